@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+use pretty_graphql::config::FormatOptions;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{self, Path, PathBuf},
+};
+use xxhash_rust::xxh3::Xxh3;
+
+/// Cache of formatted files from previous runs.
+///
+/// Each entry is the hash of file content and format options when that file was formatted,
+/// so a file can be skipped if neither of them has changed since then.
+pub(crate) struct Cache {
+    path: PathBuf,
+    files: BTreeMap<PathBuf, String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CacheFile {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl Cache {
+    /// Load cache from the given path.
+    /// Missing or invalid cache file is treated as empty cache.
+    pub(crate) fn load(path: &Path) -> Self {
+        let cache_file = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path: path.to_owned(),
+            files: cache_file.files,
+        }
+    }
+
+    pub(crate) fn is_formatted(&self, file: &Path, hash: &str) -> bool {
+        path::absolute(file)
+            .ok()
+            .and_then(|file| self.files.get(&file))
+            .is_some_and(|cached| cached == hash)
+    }
+
+    pub(crate) fn insert(&mut self, file: &Path, hash: String) -> Result<()> {
+        self.files.insert(path::absolute(file)?, hash);
+        Ok(())
+    }
+
+    pub(crate) fn save(self) -> Result<()> {
+        let content = serde_json::to_string(&CacheFile { files: self.files })?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("failed to write cache file '{}'", self.path.display()))
+    }
+}
+
+/// Hash file content with format options, JSON pointers and version of this tool,
+/// since formatting result may change after upgrading.
+pub(crate) fn hash(content: &str, options: &FormatOptions, json_pointers: &[String]) -> String {
+    let mut hasher = Xxh3::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update(&[0]);
+    hasher.update(
+        serde_json::to_string(options)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    hasher.update(&[0]);
+    for pointer in json_pointers {
+        hasher.update(pointer.as_bytes());
+        hasher.update(&[0]);
+    }
+    hasher.update(content.as_bytes());
+    format!("{:016x}", hasher.digest())
+}
@@ -0,0 +1,286 @@
+use anyhow::{Context, Result, bail};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use pretty_graphql::config::FormatOptions;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{self, Path, PathBuf},
+    rc::Rc,
+};
+
+/// Names of configuration files that will be searched in each directory.
+const CONFIG_FILE_NAMES: [&str; 2] = ["pretty_graphql.json", ".pretty-graphql.toml"];
+
+/// Names of Prettier configuration files in JSON or YAML that are supported.
+const PRETTIERRC_NAMES: [&str; 4] = [
+    ".prettierrc",
+    ".prettierrc.json",
+    ".prettierrc.yaml",
+    ".prettierrc.yml",
+];
+
+/// Resolve format options for each file.
+///
+/// Configuration files are searched from the directory of the file up to the root,
+/// and options in closer configuration files take precedence.
+/// Layout options from `.editorconfig` files are used unless they're set in configuration files.
+/// If a configuration file is specified explicitly, searching will be disabled.
+///
+/// In Prettier compatible mode, Prettier preset is used as default options,
+/// and options from the closest `.prettierrc` file take precedence over `.editorconfig` files.
+pub(crate) struct ConfigResolver {
+    explicit: Option<Rc<ConfigFile>>,
+    prettier: bool,
+    /// Configuration file in each visited directory.
+    dirs: HashMap<PathBuf, Option<Rc<ConfigFile>>>,
+    /// The closest `.prettierrc` file for each visited directory.
+    prettierrc: HashMap<PathBuf, Option<Rc<Prettierrc>>>,
+}
+
+impl ConfigResolver {
+    pub(crate) fn new(explicit: Option<&Path>, prettier: bool) -> Result<Self> {
+        let explicit = explicit
+            .map(|path| ConfigFile::load(&path::absolute(path)?).map(Rc::new))
+            .transpose()?;
+        Ok(Self {
+            explicit,
+            prettier,
+            dirs: HashMap::new(),
+            prettierrc: HashMap::new(),
+        })
+    }
+
+    /// Resolve format options for the given file. The file doesn't need to exist.
+    pub(crate) fn resolve(&mut self, file: &Path) -> Result<FormatOptions> {
+        let file = path::absolute(file)?;
+        let config_files = if let Some(config_file) = &self.explicit {
+            vec![config_file.clone()]
+        } else {
+            let mut config_files = vec![];
+            for dir in file.ancestors().skip(1) {
+                if let Some(config_file) = self.find_in_dir(dir)? {
+                    config_files.push(config_file);
+                }
+            }
+            config_files.reverse();
+            config_files
+        };
+
+        // `.editorconfig` files have the lowest priority
+        let mut defaults = if self.prettier {
+            FormatOptions::prettier()
+        } else {
+            FormatOptions::default()
+        };
+        defaults
+            .layout
+            .apply_editorconfig(&file)
+            .with_context(|| format!("failed to resolve .editorconfig for '{}'", file.display()))?;
+        if self.prettier
+            && let Some(dir) = file.parent()
+            && let Some(prettierrc) = self.find_prettierrc(dir)?
+        {
+            defaults
+                .apply_prettierrc(&prettierrc.content)
+                .with_context(|| {
+                    format!(
+                        "failed to parse config file '{}'",
+                        prettierrc.path.display()
+                    )
+                })?;
+        }
+        let mut options = match serde_json::to_value(defaults)? {
+            Value::Object(options) => options,
+            _ => Map::new(),
+        };
+        for config_file in &config_files {
+            merge(&mut options, &config_file.options);
+            let relative = file.strip_prefix(&config_file.dir).unwrap_or(&file);
+            config_file
+                .overrides
+                .iter()
+                .filter(|o| o.is_match(relative))
+                .for_each(|o| merge(&mut options, &o.options));
+        }
+        serde_json::from_value(Value::Object(options)).with_context(|| {
+            let paths = config_files
+                .iter()
+                .map(|config_file| format!("'{}'", config_file.path.display()))
+                .collect::<Vec<_>>();
+            format!("invalid options from config files {}", paths.join(", "))
+        })
+    }
+
+    fn find_in_dir(&mut self, dir: &Path) -> Result<Option<Rc<ConfigFile>>> {
+        if let Some(config_file) = self.dirs.get(dir) {
+            return Ok(config_file.clone());
+        }
+        let mut paths = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .filter(|path| path.is_file());
+        let config_file = match (paths.next(), paths.next()) {
+            (Some(path), None) => Some(Rc::new(ConfigFile::load(&path)?)),
+            (Some(_), Some(_)) => bail!(
+                "found multiple config files in '{}', please keep only one of {}",
+                dir.display(),
+                CONFIG_FILE_NAMES.join(" and ")
+            ),
+            _ => None,
+        };
+        self.dirs.insert(dir.to_owned(), config_file.clone());
+        Ok(config_file)
+    }
+
+    fn find_prettierrc(&mut self, dir: &Path) -> Result<Option<Rc<Prettierrc>>> {
+        if let Some(prettierrc) = self.prettierrc.get(dir) {
+            return Ok(prettierrc.clone());
+        }
+        let prettierrc = if let Some(path) = PRETTIERRC_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read config file '{}'", path.display()))?;
+            Some(Rc::new(Prettierrc { path, content }))
+        } else if let Some(parent) = dir.parent() {
+            self.find_prettierrc(parent)?
+        } else {
+            None
+        };
+        self.prettierrc.insert(dir.to_owned(), prettierrc.clone());
+        Ok(prettierrc)
+    }
+}
+
+/// Prettier configuration file in JSON or YAML,
+/// which is parsed by [`FormatOptions::apply_prettierrc`].
+struct Prettierrc {
+    path: PathBuf,
+    content: String,
+}
+
+struct ConfigFile {
+    path: PathBuf,
+    /// Directory that globs of overrides are relative to.
+    dir: PathBuf,
+    options: Map<String, Value>,
+    overrides: Vec<Override>,
+}
+
+#[derive(Deserialize)]
+struct RawConfigFile {
+    #[serde(default)]
+    overrides: Vec<RawOverride>,
+    #[serde(flatten)]
+    options: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct RawOverride {
+    files: Vec<String>,
+    #[serde(default)]
+    options: Map<String, Value>,
+}
+
+impl ConfigFile {
+    fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file '{}'", path.display()))?;
+        let raw = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str::<RawConfigFile>(&content).map_err(anyhow::Error::from)
+        } else {
+            serde_json::from_str::<RawConfigFile>(&content).map_err(anyhow::Error::from)
+        }
+        .with_context(|| format!("failed to parse config file '{}'", path.display()))?;
+
+        let overrides = raw
+            .overrides
+            .into_iter()
+            .map(|raw| {
+                let mut by_path = GlobSetBuilder::new();
+                let mut by_name = GlobSetBuilder::new();
+                for pattern in &raw.files {
+                    let glob = GlobBuilder::new(pattern.strip_prefix("./").unwrap_or(pattern))
+                        .literal_separator(true)
+                        .build()
+                        .with_context(|| {
+                            format!("invalid glob in config file '{}'", path.display())
+                        })?;
+                    // patterns without slashes match file names in any directory
+                    if pattern.contains('/') {
+                        by_path.add(glob);
+                    } else {
+                        by_name.add(glob);
+                    }
+                }
+                Ok(Override {
+                    by_path: by_path.build()?,
+                    by_name: by_name.build()?,
+                    options: flatten(raw.options),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            path: path.to_owned(),
+            dir: path.parent().map(Path::to_owned).unwrap_or_default(),
+            options: flatten(raw.options),
+            overrides,
+        })
+    }
+}
+
+struct Override {
+    by_path: GlobSet,
+    by_name: GlobSet,
+    options: Map<String, Value>,
+}
+
+impl Override {
+    fn is_match(&self, path: &Path) -> bool {
+        self.by_path.is_match(path)
+            || path
+                .file_name()
+                .is_some_and(|name| self.by_name.is_match(name))
+    }
+}
+
+/// Merge options into existing options, overwriting options with the same name.
+///
+/// Since options can be written in different cases (`indentWidth` or `indent_width`),
+/// keys are compared without case, underscores and dots.
+fn merge(options: &mut Map<String, Value>, other: &Map<String, Value>) {
+    for (key, value) in other {
+        let normalized = normalize_key(key);
+        options.retain(|existing, _| normalize_key(existing) != normalized);
+        options.insert(key.clone(), value.clone());
+    }
+}
+
+/// Flatten nested objects into dotted keys, since none of options are objects.
+/// For example, `fieldsDefinition.comma = "always"` in TOML is parsed as a nested table.
+fn flatten(options: Map<String, Value>) -> Map<String, Value> {
+    let mut flattened = Map::new();
+    for (key, value) in options {
+        if let Value::Object(nested) = value {
+            flattened.extend(
+                flatten(nested)
+                    .into_iter()
+                    .map(|(nested_key, value)| (format!("{key}.{nested_key}"), value)),
+            );
+        } else {
+            flattened.insert(key, value);
+        }
+    }
+    flattened
+}
+
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '.')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
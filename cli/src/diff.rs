@@ -0,0 +1,51 @@
+use similar::{ChangeTag, TextDiff};
+use std::fmt::Write;
+
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Generate unified diff between source code and formatted code,
+/// optionally colored with ANSI escape sequences.
+pub(crate) fn unified_diff(
+    path: &str,
+    original: &str,
+    formatted: &str,
+    context: usize,
+    color: bool,
+) -> String {
+    let paint = |output: &mut String, style: &str, line: &str| {
+        if color && !style.is_empty() {
+            let _ = writeln!(output, "{style}{line}{RESET}");
+        } else {
+            let _ = writeln!(output, "{line}");
+        }
+    };
+
+    let mut output = String::new();
+    let diff = TextDiff::from_lines(original, formatted);
+    paint(&mut output, BOLD, &format!("--- {path}\t(original)"));
+    paint(&mut output, BOLD, &format!("+++ {path}\t(formatted)"));
+    for hunk in diff.unified_diff().context_radius(context).iter_hunks() {
+        paint(&mut output, CYAN, &hunk.header().to_string());
+        for change in hunk.iter_changes() {
+            let (sign, style) = match change.tag() {
+                ChangeTag::Delete => ('-', RED),
+                ChangeTag::Insert => ('+', GREEN),
+                ChangeTag::Equal => (' ', ""),
+            };
+            let value = change.value();
+            paint(
+                &mut output,
+                style,
+                &format!("{sign}{}", value.strip_suffix('\n').unwrap_or(value)),
+            );
+            if change.missing_newline() {
+                output.push_str("\\ No newline at end of file\n");
+            }
+        }
+    }
+    output
+}
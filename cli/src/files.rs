@@ -0,0 +1,144 @@
+use anyhow::{Result, bail};
+use globset::GlobBuilder;
+use ignore::{
+    WalkBuilder,
+    gitignore::{Gitignore, GitignoreBuilder},
+};
+use std::{
+    env, io,
+    path::{self, Component, Path, PathBuf},
+};
+
+/// Collect files to be formatted from command line arguments.
+///
+/// Files that are specified explicitly will always be included.
+/// Directories will be searched recursively for files with specified extensions,
+/// and glob patterns will be expanded.
+/// Both of them respect `.gitignore` and skip hidden files.
+pub(crate) fn collect_files(patterns: &[String], extensions: &[String]) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for pattern in patterns {
+        let path = Path::new(pattern);
+        if is_glob(pattern) {
+            let matcher = GlobBuilder::new(pattern.strip_prefix("./").unwrap_or(pattern))
+                .literal_separator(true)
+                .build()?
+                .compile_matcher();
+            let base = glob_base(path);
+            walk(&base, &mut files, |file| {
+                matcher.is_match(file.strip_prefix(".").unwrap_or(file))
+            });
+        } else if path.is_dir() {
+            walk(path, &mut files, |file| {
+                file.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| extensions.iter().any(|expected| expected == ext))
+            });
+        } else if path.is_file() {
+            files.push(path.to_owned());
+        } else {
+            bail!("path '{pattern}' doesn't exist");
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Check if the given file would be skipped when searching directories,
+/// by `.ignore`, `.gitignore`, `.git/info/exclude` or global gitignore files,
+/// or because it's hidden. The file doesn't need to exist.
+pub(crate) fn is_ignored(path: &Path) -> io::Result<bool> {
+    let path = path::absolute(path)?;
+    // directory searching starts from current directory, which itself can be hidden
+    let cwd = env::current_dir()?;
+    let is_hidden = path
+        .strip_prefix(&cwd)
+        .unwrap_or(&path)
+        .components()
+        .any(|component| match component {
+            Component::Normal(name) => name.to_str().is_some_and(|name| name.starts_with('.')),
+            _ => false,
+        });
+    if is_hidden {
+        return Ok(true);
+    }
+
+    let repo_root = path.ancestors().find(|dir| dir.join(".git").exists());
+    let mut matchers = vec![];
+    for dir in path.ancestors().skip(1) {
+        // `.gitignore` files only take effect inside Git repositories
+        let in_repo = repo_root.is_some_and(|root| dir.starts_with(root));
+        matchers.extend(
+            [".ignore", ".gitignore"]
+                .into_iter()
+                .filter(|name| in_repo || *name == ".ignore")
+                .map(|name| Gitignore::new(dir.join(name)).0),
+        );
+    }
+    if let Some(root) = repo_root {
+        let mut exclude = GitignoreBuilder::new(root);
+        exclude.add(root.join(".git/info/exclude"));
+        matchers.push(exclude.build().unwrap_or_else(|_| Gitignore::empty()));
+        matchers.push(GitignoreBuilder::new(root).build_global().0);
+    }
+    for matcher in matchers {
+        let matched = matcher.matched_path_or_any_parents(&path, false);
+        if matched.is_ignore() {
+            return Ok(true);
+        } else if matched.is_whitelist() {
+            return Ok(false);
+        }
+    }
+    Ok(false)
+}
+
+/// Paths that should be watched for the given command line arguments.
+pub(crate) fn watch_roots(patterns: &[String]) -> Vec<PathBuf> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            let path = Path::new(pattern);
+            if is_glob(pattern) {
+                glob_base(path)
+            } else {
+                path.to_owned()
+            }
+        })
+        .collect()
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>, filter: impl Fn(&Path) -> bool) {
+    files.extend(
+        WalkBuilder::new(dir)
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_type()
+                    .is_some_and(|file_type| file_type.is_file())
+            })
+            .map(|entry| entry.into_path())
+            .filter(|path| filter(path)),
+    );
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Find the longest directory path of the glob pattern that doesn't contain glob syntax.
+fn glob_base(pattern: &Path) -> PathBuf {
+    let base = pattern
+        .components()
+        .take_while(|component| match component {
+            Component::Normal(s) => !s.to_str().is_some_and(is_glob),
+            _ => true,
+        })
+        .collect::<PathBuf>();
+    if base.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        base
+    }
+}
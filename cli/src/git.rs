@@ -0,0 +1,109 @@
+use anyhow::{Context, Result, bail};
+use pretty_graphql::{config::FormatOptions, format_definitions, try_format_text};
+use std::{ops::Range, path::Path, process::Command};
+
+/// Format only top-level definitions that touch lines changed since the given Git revision,
+/// including uncommitted changes. The rest of source input will be left as is.
+///
+/// Untracked files will be formatted entirely.
+pub(crate) fn format_changed(
+    rev: &str,
+    path: &Path,
+    input: &str,
+    options: &FormatOptions,
+) -> Result<Result<String, pretty_graphql::Error>> {
+    // this also makes sure there're no syntax errors before formatting ranges
+    let formatted = try_format_text(input, options);
+    let Some(changed) = changed_lines(rev, path)? else {
+        return Ok(formatted);
+    };
+    if formatted.is_err() {
+        return Ok(formatted);
+    }
+
+    let line_starts = line_starts(input);
+    let ranges = changed
+        .iter()
+        .map(|lines| to_byte_range(lines, &line_starts, input.len()))
+        .collect::<Vec<_>>();
+    Ok(Ok(format_definitions(input, &ranges, options)))
+}
+
+/// Find 1-based line ranges of the given file that are changed since the given revision.
+/// Ranges for deleted lines are empty ranges at lines after them.
+///
+/// Returns `None` if the file isn't tracked by Git.
+fn changed_lines(rev: &str, path: &Path) -> Result<Option<Vec<Range<usize>>>> {
+    let diff = git(
+        path,
+        &["diff", "--no-color", "--no-ext-diff", "-U0", rev, "--"],
+    )?;
+    if diff.is_empty() {
+        let tracked = git(path, &["ls-files", "--error-unmatch", "--"]).is_ok();
+        return Ok(tracked.then(Vec::new));
+    }
+
+    diff.lines()
+        .filter_map(|line| line.strip_prefix("@@ "))
+        .map(|header| {
+            let new = header
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix('+'))
+                .with_context(|| format!("invalid hunk header '@@ {header}'"))?;
+            let (start, count) = new.split_once(',').unwrap_or((new, "1"));
+            let start = start.parse::<usize>()?;
+            let count = count.parse::<usize>()?;
+            if count == 0 {
+                Ok(start + 1..start + 1)
+            } else {
+                Ok(start..start + count)
+            }
+        })
+        .collect::<Result<_>>()
+        .map(Some)
+}
+
+/// Run Git command in the directory of the given file with the file name as the last argument.
+fn git(path: &Path, args: &[&str]) -> Result<String> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let output = Command::new("git")
+        .args(args)
+        .arg(path.file_name().unwrap_or_default())
+        .current_dir(dir)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed for '{}': {}",
+            args[0],
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Byte offsets where each line starts.
+fn line_starts(input: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(input.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+/// Convert 1-based line range to byte range, excluding the line break of the last line.
+fn to_byte_range(lines: &Range<usize>, line_starts: &[usize], len: usize) -> Range<usize> {
+    let start = line_starts
+        .get(lines.start.saturating_sub(1))
+        .copied()
+        .unwrap_or(len);
+    if lines.is_empty() {
+        return start..start;
+    }
+    let end = line_starts
+        .get(lines.end - 1)
+        .map_or(len, |next_line_start| next_line_start - 1);
+    start..end.max(start)
+}
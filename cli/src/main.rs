@@ -0,0 +1,360 @@
+use anyhow::{Context, Result};
+use cache::Cache;
+use clap::{Parser, ValueEnum};
+use config::ConfigResolver;
+use pretty_graphql::config::FormatOptions;
+use rayon::prelude::*;
+use source::Formatted;
+use std::{
+    fs,
+    io::{self, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+mod cache;
+mod config;
+mod diff;
+mod files;
+mod git;
+mod source;
+mod watch;
+
+#[derive(Parser)]
+#[command(
+    name = "pretty-graphql",
+    version,
+    about = "Configurable GraphQL formatter."
+)]
+struct Args {
+    /// Files, directories or glob patterns to format.
+    #[arg(default_value = ".")]
+    paths: Vec<String>,
+
+    /// Path to configuration file in JSON or TOML.
+    /// If not specified, `pretty_graphql.json` or `.pretty-graphql.toml`
+    /// will be searched from the directory of each file up to the root.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Use Prettier compatible options by default,
+    /// and read options from `.prettierrc` files.
+    #[arg(long)]
+    prettier: bool,
+
+    /// File extensions to look for when searching directories.
+    #[arg(
+        long = "ext",
+        value_name = "EXT",
+        value_delimiter = ',',
+        default_values = ["graphql", "gql"]
+    )]
+    extensions: Vec<String>,
+
+    /// JSON pointers to string values that will be formatted in JSON files,
+    /// where `*` matches any key or index.
+    #[arg(
+        long = "json-pointer",
+        value_name = "POINTER",
+        default_values = ["/*"]
+    )]
+    json_pointers: Vec<String>,
+
+    /// Read source code from stdin and write formatted code to stdout.
+    #[arg(long, conflicts_with = "paths")]
+    stdin: bool,
+
+    /// Path of the file that source code from stdin belongs to.
+    /// It's used for resolving config files and ignore rules, and implies `--stdin`.
+    /// Ignored files will be printed to stdout unchanged.
+    #[arg(long, value_name = "PATH", conflicts_with = "paths")]
+    stdin_filepath: Option<PathBuf>,
+
+    /// Check if files are formatted without writing them.
+    /// Paths of unformatted files will be printed.
+    #[arg(long)]
+    check: bool,
+
+    /// Skip files that are formatted in previous runs
+    /// and haven't been changed since then.
+    #[arg(long)]
+    cache: bool,
+
+    /// Path to cache file.
+    #[arg(long, value_name = "PATH", default_value = ".pretty-graphql-cache")]
+    cache_location: PathBuf,
+
+    /// Watch files and format them when they're changed.
+    /// Paths of formatted files will be printed.
+    #[arg(long, conflicts_with_all = ["check", "diff", "stdin", "stdin_filepath"])]
+    watch: bool,
+
+    /// Only format top-level definitions that touch lines changed since the given Git revision.
+    /// Files of other languages with embedded GraphQL code will be skipped with warnings.
+    #[arg(
+        long,
+        value_name = "REV",
+        conflicts_with_all = ["cache", "watch", "stdin", "stdin_filepath"]
+    )]
+    since: Option<String>,
+
+    /// Print unified diff of unformatted files without writing them.
+    #[arg(long, conflicts_with = "check")]
+    diff: bool,
+
+    /// Count of context lines in diff.
+    #[arg(long, value_name = "LINES", default_value_t = 3)]
+    diff_context: usize,
+
+    /// Whether to print colored diff.
+    #[arg(long, value_name = "WHEN", default_value = "auto")]
+    color: ColorChoice,
+}
+
+#[derive(Clone, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/// All files are formatted.
+const EXIT_CLEAN: u8 = 0;
+/// Some files aren't formatted. This is only used in check mode or diff mode.
+const EXIT_UNFORMATTED: u8 = 1;
+/// Some files have syntax errors, or other errors occurred.
+const EXIT_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(args: Args) -> Result<u8> {
+    let mut config = ConfigResolver::new(args.config.as_deref(), args.prettier)?;
+
+    let color = match args.color {
+        ColorChoice::Auto => io::stdout().is_terminal(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+
+    if args.stdin || args.stdin_filepath.is_some() {
+        return run_stdin(&args, &mut config, color);
+    }
+
+    if args.watch {
+        return watch::watch(&args);
+    }
+
+    let jobs = files::collect_files(&args.paths, &args.extensions)?
+        .into_iter()
+        .map(|path| {
+            let options = config.resolve(&path)?;
+            Ok((path, options))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut cache = args.cache.then(|| Cache::load(&args.cache_location));
+
+    // files are formatted in parallel, but reported in order
+    let results = jobs
+        .par_iter()
+        .map(|(path, options)| format_file(&args, path, options, cache.as_ref(), color))
+        .collect::<Vec<_>>();
+
+    let mut unformatted = 0;
+    let mut syntax_errors = 0;
+    for ((path, _), result) in jobs.iter().zip(results) {
+        let (outcome, warnings, hash) = result?;
+        for warning in warnings {
+            eprintln!("warning: {}:\n{warning}", path.display());
+        }
+        match outcome {
+            Outcome::Formatted => {}
+            Outcome::Unformatted(report) => {
+                if args.check {
+                    println!("{}", path.display());
+                    unformatted += 1;
+                } else if args.diff {
+                    print!("{report}");
+                    unformatted += 1;
+                }
+            }
+            Outcome::SyntaxError(error) => {
+                eprintln!("{}:\n{error}", path.display());
+                syntax_errors += 1;
+            }
+        }
+        if let (Some(cache), Some(hash)) = (&mut cache, hash) {
+            cache.insert(path, hash)?;
+        }
+    }
+    if let Some(cache) = cache {
+        cache.save()?;
+    }
+
+    if unformatted > 0 {
+        eprintln!("{unformatted} file(s) are not formatted.");
+    }
+    if syntax_errors > 0 {
+        eprintln!("{syntax_errors} file(s) have syntax errors.");
+        Ok(EXIT_ERROR)
+    } else if unformatted > 0 {
+        Ok(EXIT_UNFORMATTED)
+    } else {
+        Ok(EXIT_CLEAN)
+    }
+}
+
+enum Outcome {
+    Formatted,
+    /// File isn't formatted, with unified diff in diff mode.
+    /// It has been written if not in check mode or diff mode.
+    Unformatted(String),
+    SyntaxError(String),
+}
+
+/// Format a single file, and return warnings with the hash for caching if file is formatted on disk.
+fn format_file(
+    args: &Args,
+    path: &Path,
+    options: &FormatOptions,
+    cache: Option<&Cache>,
+    color: bool,
+) -> Result<(Outcome, Vec<String>, Option<String>)> {
+    let input =
+        fs::read_to_string(path).with_context(|| format!("failed to read '{}'", path.display()))?;
+    let input_hash = cache.map(|_| cache::hash(&input, options, &args.json_pointers));
+    if let (Some(cache), Some(hash)) = (cache, &input_hash)
+        && cache.is_formatted(path, hash)
+    {
+        return Ok((Outcome::Formatted, vec![], input_hash));
+    }
+
+    let Formatted {
+        output,
+        errors,
+        warnings,
+    } = match &args.since {
+        // formatting only changed lines isn't supported for embedded GraphQL code,
+        // so such files are left as is instead of being formatted entirely
+        Some(_) if source::is_host_file(path) => Formatted {
+            output: input.clone(),
+            errors: vec![],
+            warnings: vec!["skipped, since `--since` only supports GraphQL files".into()],
+        },
+        Some(rev) => match git::format_changed(rev, path, &input, options)? {
+            Ok(output) => Formatted {
+                output,
+                errors: vec![],
+                warnings: vec![],
+            },
+            Err(error) => Formatted {
+                output: input.clone(),
+                errors: vec![error.to_string()],
+                warnings: vec![],
+            },
+        },
+        _ => source::format_source(Some(path), &input, &args.json_pointers, options),
+    };
+
+    let (outcome, hash) = if output == input {
+        (Outcome::Formatted, input_hash)
+    } else if args.check {
+        (Outcome::Unformatted(String::new()), None)
+    } else if args.diff {
+        let diff = diff::unified_diff(
+            &path.display().to_string(),
+            &input,
+            &output,
+            args.diff_context,
+            color,
+        );
+        (Outcome::Unformatted(diff), None)
+    } else {
+        let output_hash = cache.map(|_| cache::hash(&output, options, &args.json_pointers));
+        fs::write(path, output).with_context(|| format!("failed to write '{}'", path.display()))?;
+        (Outcome::Unformatted(String::new()), output_hash)
+    };
+    // code blocks without errors in files of other languages may have been formatted
+    if !errors.is_empty() {
+        Ok((Outcome::SyntaxError(errors.join("\n")), warnings, None))
+    } else if !warnings.is_empty() {
+        // don't cache it, so warnings will be reported next time
+        Ok((outcome, warnings, None))
+    } else {
+        Ok((outcome, warnings, hash))
+    }
+}
+
+fn run_stdin(args: &Args, config: &mut ConfigResolver, color: bool) -> Result<u8> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .context("failed to read stdin")?;
+    let name = args
+        .stdin_filepath
+        .as_ref()
+        .map_or_else(|| "<stdin>".into(), |path| path.display().to_string());
+
+    if let Some(path) = &args.stdin_filepath
+        && files::is_ignored(path)
+            .with_context(|| format!("failed to resolve path '{}'", path.display()))?
+    {
+        if !args.check && !args.diff {
+            print!("{input}");
+        }
+        return Ok(EXIT_CLEAN);
+    }
+
+    // without a file path, treat it as a file in current directory
+    let options = config.resolve(
+        args.stdin_filepath
+            .as_deref()
+            .unwrap_or(Path::new("<stdin>")),
+    )?;
+    let Formatted {
+        output,
+        errors,
+        warnings,
+    } = source::format_source(
+        args.stdin_filepath.as_deref(),
+        &input,
+        &args.json_pointers,
+        &options,
+    );
+    for warning in warnings {
+        eprintln!("warning: {name}:\n{warning}");
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{name}:\n{error}");
+        }
+        return Ok(EXIT_ERROR);
+    }
+    let code = if output != input && (args.check || args.diff) {
+        EXIT_UNFORMATTED
+    } else {
+        EXIT_CLEAN
+    };
+    if args.check {
+        if output != input {
+            println!("{name}");
+        }
+    } else if args.diff {
+        if output != input {
+            print!(
+                "{}",
+                diff::unified_diff(&name, &input, &output, args.diff_context, color)
+            );
+        }
+    } else {
+        print!("{output}");
+    }
+    Ok(code)
+}
@@ -0,0 +1,111 @@
+use pretty_graphql::{
+    config::FormatOptions,
+    embed::{self, EmbedOutput},
+    try_format_text,
+};
+use std::path::Path;
+
+/// Extensions of JavaScript and TypeScript files.
+const JS_EXTENSIONS: [&str; 8] = ["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+/// Extensions of Markdown files.
+const MARKDOWN_EXTENSIONS: [&str; 3] = ["md", "mdx", "markdown"];
+/// Extensions of HTTP request files.
+const HTTP_EXTENSIONS: [&str; 2] = ["http", "rest"];
+/// Extensions of Python files.
+const PYTHON_EXTENSIONS: [&str; 2] = ["py", "pyi"];
+/// Extensions of JSON files, such as persisted query manifests.
+const JSON_EXTENSIONS: [&str; 1] = ["json"];
+
+/// Check if the file is written in other languages that GraphQL code may be embedded in.
+pub(crate) fn is_host_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            JS_EXTENSIONS.contains(&ext)
+                || MARKDOWN_EXTENSIONS.contains(&ext)
+                || HTTP_EXTENSIONS.contains(&ext)
+                || PYTHON_EXTENSIONS.contains(&ext)
+                || JSON_EXTENSIONS.contains(&ext)
+        })
+}
+
+pub(crate) struct Formatted {
+    pub(crate) output: String,
+    pub(crate) errors: Vec<String>,
+    /// Code blocks in Markdown may be incomplete examples intentionally,
+    /// so they're reported as warnings instead of errors.
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Format source code according to its file type.
+///
+/// For files of other languages, GraphQL code embedded in them will be formatted.
+/// Code blocks that can't be formatted will be left as is and reported,
+/// while other blocks are still formatted.
+/// For JSON files, only string values at the given JSON pointers will be formatted.
+pub(crate) fn format_source(
+    path: Option<&Path>,
+    input: &str,
+    json_pointers: &[String],
+    options: &FormatOptions,
+) -> Formatted {
+    let ext = path
+        .and_then(|path| path.extension())
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+    if JS_EXTENSIONS.contains(&ext)
+        || HTTP_EXTENSIONS.contains(&ext)
+        || PYTHON_EXTENSIONS.contains(&ext)
+        || JSON_EXTENSIONS.contains(&ext)
+    {
+        let embedded = if JS_EXTENSIONS.contains(&ext) {
+            embed::format_js(input, options)
+        } else if HTTP_EXTENSIONS.contains(&ext) {
+            embed::format_http(input, options)
+        } else if PYTHON_EXTENSIONS.contains(&ext) {
+            embed::format_python(input, options)
+        } else {
+            let pointers = json_pointers.iter().map(String::as_str).collect::<Vec<_>>();
+            embed::format_json(input, &pointers, options)
+        };
+        Formatted {
+            errors: report_skipped(input, &embedded),
+            output: embedded.output,
+            warnings: vec![],
+        }
+    } else if MARKDOWN_EXTENSIONS.contains(&ext) {
+        let embedded = embed::format_markdown(input, options);
+        Formatted {
+            warnings: report_skipped(input, &embedded),
+            output: embedded.output,
+            errors: vec![],
+        }
+    } else {
+        match try_format_text(input, options) {
+            Ok(output) => Formatted {
+                output,
+                errors: vec![],
+                warnings: vec![],
+            },
+            Err(error) => Formatted {
+                output: input.to_owned(),
+                errors: vec![error.to_string()],
+                warnings: vec![],
+            },
+        }
+    }
+}
+
+fn report_skipped(input: &str, embedded: &EmbedOutput) -> Vec<String> {
+    embedded
+        .skipped
+        .iter()
+        .map(|skipped| {
+            let line = input[..skipped.range.start].matches('\n').count() + 1;
+            format!(
+                "in GraphQL code starting at line {line}:\n{}",
+                skipped.error
+            )
+        })
+        .collect()
+}
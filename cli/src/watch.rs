@@ -0,0 +1,110 @@
+use crate::{Args, config::ConfigResolver, files, source};
+use anyhow::{Context, Result};
+use notify_debouncer_mini::{DebounceEventResult, new_debouncer, notify::RecursiveMode};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    slice,
+    sync::mpsc,
+    time::Duration,
+};
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Watch files and format them when they're changed.
+/// This never returns unless watching can't be started, and errors after that are only logged.
+pub(crate) fn watch(args: &Args) -> Result<u8> {
+    let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+    let mut debouncer = new_debouncer(Duration::from_millis(200), tx)?;
+    for root in files::watch_roots(&args.paths) {
+        // event paths are based on watched paths, and they're compared with canonical paths later
+        let root = fs::canonicalize(&root)
+            .with_context(|| format!("path '{}' doesn't exist", root.display()))?;
+        // editors may save files by renaming new files over them, which drops watches on them,
+        // so parent directories of files are watched instead
+        let (root, mode) = match root.parent() {
+            Some(parent) if root.is_file() => (parent.to_owned(), RecursiveMode::NonRecursive),
+            _ => (root, RecursiveMode::Recursive),
+        };
+        debouncer
+            .watcher()
+            .watch(&root, mode)
+            .with_context(|| format!("failed to watch '{}'", root.display()))?;
+    }
+    eprintln!("Watching for changes...");
+
+    // hash of content that was written by ourselves for each file
+    let mut written = HashMap::<PathBuf, u64>::new();
+    for events in rx {
+        let changed = match events {
+            Ok(events) => events
+                .into_iter()
+                .map(|event| event.path)
+                .collect::<HashSet<_>>(),
+            Err(error) => {
+                eprintln!("error: {error}");
+                continue;
+            }
+        };
+        // files are collected again since new files may be created,
+        // and files may be deleted, which shouldn't stop watching others
+        let files = args
+            .paths
+            .iter()
+            .filter_map(|pattern| {
+                files::collect_files(slice::from_ref(pattern), &args.extensions)
+                    .inspect_err(|error| eprintln!("error: {error:#}"))
+                    .ok()
+            })
+            .flatten()
+            .collect::<BTreeSet<_>>();
+        // config files are also loaded again since they may be changed
+        let mut config = match ConfigResolver::new(args.config.as_deref(), args.prettier) {
+            Ok(config) => config,
+            Err(error) => {
+                eprintln!("error: {error:#}");
+                continue;
+            }
+        };
+        for path in files {
+            let Ok(canonical) = fs::canonicalize(&path) else {
+                continue;
+            };
+            if !changed.contains(&canonical) {
+                continue;
+            }
+            let Ok(input) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if written.get(&canonical) == Some(&xxh3_64(input.as_bytes())) {
+                continue;
+            }
+            let options = match config.resolve(&path) {
+                Ok(options) => options,
+                Err(error) => {
+                    eprintln!("error: {error:#}");
+                    continue;
+                }
+            };
+            let formatted =
+                source::format_source(Some(&path), &input, &args.json_pointers, &options);
+            for error in formatted.errors {
+                eprintln!("{}:\n{error}", path.display());
+            }
+            for warning in formatted.warnings {
+                eprintln!("warning: {}:\n{warning}", path.display());
+            }
+            let output = formatted.output;
+            if output == input {
+                continue;
+            }
+            if let Err(error) = fs::write(&path, &output) {
+                eprintln!("error: failed to write '{}': {error}", path.display());
+                continue;
+            }
+            written.insert(canonical, xxh3_64(output.as_bytes()));
+            println!("{}", path.display());
+        }
+    }
+    Ok(crate::EXIT_CLEAN)
+}
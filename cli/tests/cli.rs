@@ -0,0 +1,694 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+use tempfile::TempDir;
+
+fn pretty_graphql(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_pretty-graphql"));
+    command.current_dir(dir);
+    command
+}
+
+fn setup() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("schema/nested")).unwrap();
+    fs::create_dir_all(dir.path().join("ignored")).unwrap();
+    fs::write(dir.path().join(".gitignore"), "ignored/\n").unwrap();
+    fs::write(dir.path().join("query.graphql"), "{ a }").unwrap();
+    fs::write(dir.path().join("schema/nested/a.gql"), "type A { a: Int }").unwrap();
+    fs::write(dir.path().join("schema/b.graphqls"), "type B { b: Int }").unwrap();
+    fs::write(dir.path().join("ignored/c.graphql"), "{ c }").unwrap();
+    fs::write(dir.path().join("readme.txt"), "{ d }").unwrap();
+    // make `.gitignore` take effect
+    fs::create_dir(dir.path().join(".git")).unwrap();
+    dir
+}
+
+#[test]
+fn format_directory() {
+    let dir = setup();
+    let status = pretty_graphql(dir.path()).status().unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("query.graphql"), "{\n  a\n}\n");
+    assert_eq!(read("schema/nested/a.gql"), "type A {\n  a: Int\n}\n");
+    assert_eq!(read("schema/b.graphqls"), "type B { b: Int }");
+    assert_eq!(read("ignored/c.graphql"), "{ c }");
+    assert_eq!(read("readme.txt"), "{ d }");
+}
+
+#[test]
+fn format_glob_and_files() {
+    let dir = setup();
+    let status = pretty_graphql(dir.path())
+        .args(["schema/**/*.graphqls", "readme.txt"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("schema/b.graphqls"), "type B {\n  b: Int\n}\n");
+    assert_eq!(read("readme.txt"), "{\n  d\n}\n");
+    assert_eq!(read("query.graphql"), "{ a }");
+    assert_eq!(read("schema/nested/a.gql"), "type A { a: Int }");
+}
+
+#[test]
+fn config_file() {
+    let dir = setup();
+    fs::write(dir.path().join("config.json"), r#"{ "indentWidth": 4 }"#).unwrap();
+    let status = pretty_graphql(dir.path())
+        .args(["--config", "config.json", "query.graphql"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("query.graphql")).unwrap(),
+        "{\n    a\n}\n"
+    );
+}
+
+#[test]
+fn syntax_error() {
+    let dir = setup();
+    fs::write(dir.path().join("query.graphql"), "{ a(b: ) }").unwrap();
+    let output = pretty_graphql(dir.path()).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("query.graphql"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("query.graphql")).unwrap(),
+        "{ a(b: ) }"
+    );
+}
+
+#[test]
+fn check() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("schema/nested/a.gql"),
+        "type A {\n  a: Int\n}\n",
+    )
+    .unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["--check", "query.graphql", "schema"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "query.graphql\n");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("query.graphql")).unwrap(),
+        "{ a }"
+    );
+
+    let output = pretty_graphql(dir.path())
+        .args(["--check", "schema"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    fs::write(dir.path().join("schema/nested/b.gql"), "type B {").unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["--check", "query.graphql", "schema"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("1 file(s) are not formatted."));
+    assert!(stderr.contains("1 file(s) have syntax errors."));
+}
+
+#[test]
+fn check_no_definitions() {
+    let dir = setup();
+    fs::write(dir.path().join("empty.graphql"), "").unwrap();
+    fs::write(dir.path().join("blank.graphql"), "\n").unwrap();
+    fs::write(dir.path().join("notes.graphql"), "# notes\n").unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["--check", "empty.graphql", "blank.graphql", "notes.graphql"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "empty.graphql\n");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("syntax errors"));
+}
+
+#[test]
+fn diff() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("query.graphql"),
+        "# a\n\n{ a }\n\n# b\n# c\n",
+    )
+    .unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["--diff", "--diff-context", "1", "query.graphql"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "--- query.graphql\t(original)
++++ query.graphql\t(formatted)
+@@ -2,3 +2,5 @@
+ 
+-{ a }
++{
++  a
++}
+ 
+"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("query.graphql")).unwrap(),
+        "# a\n\n{ a }\n\n# b\n# c\n"
+    );
+
+    let output = pretty_graphql(dir.path())
+        .args(["--diff", "--color", "always", "query.graphql"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\x1b[31m-{ a }\x1b[0m\n"));
+    assert!(stdout.contains("\x1b[32m+  a\x1b[0m\n"));
+}
+
+#[test]
+fn stdin() {
+    let dir = setup();
+    fs::create_dir(dir.path().join(".git/info")).unwrap();
+    fs::write(dir.path().join(".git/info/exclude"), "excluded/\n").unwrap();
+    let home = tempfile::tempdir().unwrap();
+    fs::create_dir(home.path().join("git")).unwrap();
+    fs::write(home.path().join("git/ignore"), "global.graphql\n").unwrap();
+    let run = |args: &[&str], input: &str| {
+        let mut child = pretty_graphql(dir.path())
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let output = run(&["--stdin"], "{ a }");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "{\n  a\n}\n");
+
+    let output = run(&["--stdin-filepath", "schema/new.graphql"], "{ a }");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "{\n  a\n}\n");
+
+    for path in [
+        "ignored/new.graphql",
+        ".github/new.graphql",
+        "excluded/new.graphql",
+        "global.graphql",
+    ] {
+        let output = run(&["--stdin-filepath", path], "{ a }");
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "{ a }", "{path}");
+    }
+
+    let output = run(&["--stdin", "--check"], "{ a }");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "<stdin>\n");
+
+    let output = run(&["--stdin"], "{ a");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn config_discovery() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("pretty_graphql.json"),
+        r#"{
+  "indentWidth": 4,
+  "overrides": [
+    { "files": ["schema/**/*.gql"], "options": { "fieldsDefinition.comma": "always" } },
+    { "files": ["*.graphql"], "options": { "indent_width": 1 } }
+  ]
+}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("schema/.pretty-graphql.toml"),
+        "indent_width = 3\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("schema/nested/a.gql"),
+        "type A { a: Int b: Int }",
+    )
+    .unwrap();
+    fs::write(dir.path().join("schema/b.gql"), "type B { b: Int }").unwrap();
+    let status = pretty_graphql(dir.path()).status().unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("query.graphql"), "{\n a\n}\n");
+    assert_eq!(
+        read("schema/nested/a.gql"),
+        "type A {\n   a: Int,\n   b: Int,\n}\n"
+    );
+    assert_eq!(read("schema/b.gql"), "type B {\n   b: Int,\n}\n");
+
+    fs::write(dir.path().join("schema/pretty_graphql.json"), "{}").unwrap();
+    let output = pretty_graphql(dir.path()).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("multiple config files"));
+}
+
+#[test]
+fn toml_dotted_keys() {
+    let dir = setup();
+    fs::write(
+        dir.path().join(".pretty-graphql.toml"),
+        r#"fieldsDefinition.comma = "always"
+
+[[overrides]]
+files = ["b.graphql"]
+options.fieldsDefinition.comma = "never"
+"#,
+    )
+    .unwrap();
+    fs::write(dir.path().join("a.graphql"), "type A { a: Int }").unwrap();
+    fs::write(dir.path().join("b.graphql"), "type B { b: Int }").unwrap();
+    let status = pretty_graphql(dir.path())
+        .args(["a.graphql", "b.graphql"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("a.graphql"), "type A {\n  a: Int,\n}\n");
+    assert_eq!(read("b.graphql"), "type B {\n  b: Int\n}\n");
+}
+
+#[test]
+fn cache() {
+    let dir = setup();
+    let status = pretty_graphql(dir.path()).arg("--cache").status().unwrap();
+    assert!(status.success());
+    let cache = fs::read_to_string(dir.path().join(".pretty-graphql-cache")).unwrap();
+    assert!(cache.contains("query.graphql"));
+    assert!(cache.contains("a.gql"));
+    assert!(!cache.contains("c.graphql"));
+
+    // changing file content or options invalidates cache
+    fs::write(dir.path().join("query.graphql"), "{ a }").unwrap();
+    let status = pretty_graphql(dir.path()).arg("--cache").status().unwrap();
+    assert!(status.success());
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("query.graphql"), "{\n  a\n}\n");
+
+    fs::write(
+        dir.path().join("pretty_graphql.json"),
+        r#"{ "indentWidth": 4 }"#,
+    )
+    .unwrap();
+    let status = pretty_graphql(dir.path()).arg("--cache").status().unwrap();
+    assert!(status.success());
+    assert_eq!(read("query.graphql"), "{\n    a\n}\n");
+
+    let status = pretty_graphql(dir.path())
+        .args(["--cache", "--cache-location", "cache.json"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(dir.path().join("cache.json").is_file());
+}
+
+#[test]
+fn watch() {
+    let dir = setup();
+    let mut child = pretty_graphql(dir.path())
+        .arg("--watch")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    assert_eq!(line, "Watching for changes...\n");
+
+    fs::write(dir.path().join("schema/nested/a.gql"), "type A { a: Int }").unwrap();
+    fs::write(dir.path().join("readme.txt"), "{ d }").unwrap();
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    let start = Instant::now();
+    while read("schema/nested/a.gql") != "type A {\n  a: Int\n}\n" {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "file isn't formatted"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+    // wait for events of our own writes
+    thread::sleep(Duration::from_millis(500));
+    child.kill().unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "./schema/nested/a.gql\n"
+    );
+    assert_eq!(read("query.graphql"), "{ a }");
+    assert_eq!(read("readme.txt"), "{ d }");
+}
+
+#[test]
+fn watch_files() {
+    let dir = setup();
+    fs::write(dir.path().join("other.graphql"), "{ b }").unwrap();
+    let mut child = pretty_graphql(dir.path())
+        .args(["--watch", "query.graphql", "other.graphql"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    assert_eq!(line, "Watching for changes...\n");
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    // save like editors do, by renaming a new file over the watched file
+    let save = |content: &str| {
+        fs::write(dir.path().join("query.graphql.tmp"), content).unwrap();
+        fs::rename(
+            dir.path().join("query.graphql.tmp"),
+            dir.path().join("query.graphql"),
+        )
+        .unwrap();
+    };
+    let wait_for = |expected: &str| {
+        let start = Instant::now();
+        while read("query.graphql") != expected {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "file isn't formatted"
+            );
+            thread::sleep(Duration::from_millis(50));
+        }
+    };
+    save("{ c }");
+    wait_for("{\n  c\n}\n");
+    save("{ d }");
+    wait_for("{\n  d\n}\n");
+    // deleting a watched file shouldn't stop watching others
+    fs::remove_file(dir.path().join("other.graphql")).unwrap();
+    thread::sleep(Duration::from_millis(500));
+    save("{ e }");
+    wait_for("{\n  e\n}\n");
+
+    assert!(child.try_wait().unwrap().is_none());
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn watch_invalid_config() {
+    let dir = setup();
+    fs::write(dir.path().join("config.json"), "{}").unwrap();
+    let mut child = pretty_graphql(dir.path())
+        .args(["--watch", "--config", "config.json", "query.graphql"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    assert_eq!(line, "Watching for changes...\n");
+
+    // config file may be invalid for a while when it's being edited
+    fs::write(dir.path().join("config.json"), "{").unwrap();
+    fs::write(dir.path().join("query.graphql"), "{ b }").unwrap();
+    thread::sleep(Duration::from_millis(500));
+    assert!(child.try_wait().unwrap().is_none());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("query.graphql")).unwrap(),
+        "{ b }"
+    );
+
+    fs::write(dir.path().join("config.json"), "{}").unwrap();
+    fs::write(dir.path().join("query.graphql"), "{ c }").unwrap();
+    let start = Instant::now();
+    while fs::read_to_string(dir.path().join("query.graphql")).unwrap() != "{\n  c\n}\n" {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "file isn't formatted"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn editorconfig() {
+    let dir = setup();
+    fs::write(
+        dir.path().join(".editorconfig"),
+        "root = true\n[*]\nindent_style = tab\n[schema/**]\nindent_style = space\nindent_size = 3\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("schema/pretty_graphql.json"),
+        r#"{ "indentWidth": 5 }"#,
+    )
+    .unwrap();
+    let status = pretty_graphql(dir.path()).status().unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("query.graphql"), "{\n\ta\n}\n");
+    assert_eq!(read("schema/nested/a.gql"), "type A {\n     a: Int\n}\n");
+}
+
+#[test]
+fn prettier() {
+    let dir = setup();
+    fs::write(
+        dir.path().join(".prettierrc"),
+        "tabWidth: 4\nbracketSpacing: false\nsemi: false\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("schema/.prettierrc.json"), "{}").unwrap();
+    fs::write(
+        dir.path().join("query.graphql"),
+        "{ a(\n  b: { c: 1 }\n  d: 2\n) }",
+    )
+    .unwrap();
+    fs::write(dir.path().join("schema/nested/a.gql"), "{ a(b: { c: 1 }) }").unwrap();
+    let status = pretty_graphql(dir.path())
+        .arg("--prettier")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("query.graphql"), "{\n    a(b: {c: 1}, d: 2)\n}\n");
+    assert_eq!(read("schema/nested/a.gql"), "{\n  a(b: { c: 1 })\n}\n");
+}
+
+#[test]
+fn since() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["init", "-q"]);
+    fs::write(
+        dir.path().join("schema.graphql"),
+        "type A { a: Int }\n\ntype B { b: Int }\n\ntype C { c: Int }\n",
+    )
+    .unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+
+    fs::write(
+        dir.path().join("schema.graphql"),
+        "type A { a: Int }\n\ntype B { b: Int  c: Int }\n\ntype C { c: Int }\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("new.graphql"), "type D { d: Int }").unwrap();
+    let status = pretty_graphql(dir.path())
+        .args(["--since", "HEAD"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(
+        read("schema.graphql"),
+        "type A { a: Int }\n\ntype B {\n  b: Int\n  c: Int\n}\n\ntype C { c: Int }\n"
+    );
+    assert_eq!(read("new.graphql"), "type D {\n  d: Int\n}\n");
+
+    // files of other languages are skipped with warnings
+    fs::write(dir.path().join("query.ts"), "const Q = gql`{ a }`;\n").unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["query.ts", "--since", "HEAD"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: query.ts:"));
+    assert_eq!(read("query.ts"), "const Q = gql`{ a }`;\n");
+
+    // two hunks in one definition, followed by an unformatted definition
+    let padding = " ".repeat(40);
+    fs::write(
+        dir.path().join("query.graphql"),
+        format!("query A {{\n  a{padding}\n  b\n  p\n  c\n}}\n\nquery   B {{ b }}\n\nquery   C {{\n    y\n}}\n"),
+    )
+    .unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "query"]);
+    fs::write(
+        dir.path().join("query.graphql"),
+        format!("query A {{\n  a{padding}\n  bb\n  p\n  cc\n}}\n\nquery   B {{ b }}\n\nquery   C {{\n    y\n}}\n"),
+    )
+    .unwrap();
+    let status = pretty_graphql(dir.path())
+        .args(["query.graphql", "--since", "HEAD"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        read("query.graphql"),
+        "query A {\n  a\n  bb\n  p\n  cc\n}\n\nquery   B { b }\n\nquery   C {\n    y\n}\n"
+    );
+}
+
+#[test]
+fn embedded_in_js() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("schema/query.ts"),
+        "export const Q = gql`{ a }`;\nexport const R = gql`{ b( }`;\n",
+    )
+    .unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["--ext", "graphql,ts"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("at line 2"));
+
+    let read = |path| fs::read_to_string(dir.path().join(path)).unwrap();
+    assert_eq!(read("query.graphql"), "{\n  a\n}\n");
+    assert_eq!(
+        read("schema/query.ts"),
+        "export const Q = gql`\n  {\n    a\n  }\n`;\nexport const R = gql`{ b( }`;\n"
+    );
+}
+
+#[test]
+fn embedded_in_markdown() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("README.md"),
+        "# Doc\n\n```graphql\n{ a }\n```\n\n```graphql\n{ ... }\n```\n",
+    )
+    .unwrap();
+    let output = pretty_graphql(dir.path())
+        .args(["README.md"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: README.md"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("README.md")).unwrap(),
+        "# Doc\n\n```graphql\n{\n  a\n}\n```\n\n```graphql\n{ ... }\n```\n"
+    );
+}
+
+#[test]
+fn embedded_in_http() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("api.http"),
+        "POST http://localhost/graphql\nX-Request-Type: GraphQL\n\n{ a }\n\n{\"b\": 1}\n",
+    )
+    .unwrap();
+    let status = pretty_graphql(dir.path()).arg("api.http").status().unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("api.http")).unwrap(),
+        "POST http://localhost/graphql\nX-Request-Type: GraphQL\n\n{\n  a\n}\n\n{\"b\": 1}\n"
+    );
+}
+
+#[test]
+fn embedded_in_json() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("relay.json"),
+        "{\n  \"b\": \"query B { b }\",\n  \"a\": \"{ a }\"\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("apollo.json"),
+        r#"{"operations":[{"id":"1","body":"{ a }"}]}"#,
+    )
+    .unwrap();
+    let status = pretty_graphql(dir.path())
+        .arg("relay.json")
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("relay.json")).unwrap(),
+        "{\n  \"b\": \"query B {\\n  b\\n}\",\n  \"a\": \"{\\n  a\\n}\"\n}\n"
+    );
+
+    let status = pretty_graphql(dir.path())
+        .args(["apollo.json", "--json-pointer", "/operations/*/body"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("apollo.json")).unwrap(),
+        r#"{"operations":[{"id":"1","body":"{\n  a\n}"}]}"#
+    );
+}
+
+#[test]
+fn embedded_in_python() {
+    let dir = setup();
+    fs::write(dir.path().join("client.py"), "q = gql(\"\"\"{ a }\"\"\")\n").unwrap();
+    let status = pretty_graphql(dir.path())
+        .args([".", "--ext", "py"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("client.py")).unwrap(),
+        "q = gql(\"\"\"\n        {\n          a\n        }\n        \"\"\")\n"
+    );
+}
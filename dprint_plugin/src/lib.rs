@@ -6,7 +6,7 @@ use dprint_core::{
     configuration::{ConfigKeyMap, GlobalConfiguration, ResolveConfigurationResult},
     plugins::{FileMatchingInfo, PluginInfo, SyncPluginHandler, SyncPluginInfo},
 };
-use pretty_graphql::{config::FormatOptions, format_text};
+use pretty_graphql::{config::FormatOptions, try_format_text};
 use std::path::Path;
 
 mod config;
@@ -58,7 +58,7 @@ impl SyncPluginHandler<FormatOptions> for PrettyGraphqlPluginHandler {
         config: &FormatOptions,
         _: impl FnMut(&Path, Vec<u8>, &ConfigKeyMap) -> Result<Option<Vec<u8>>>,
     ) -> Result<Option<Vec<u8>>> {
-        let format_result = format_text(std::str::from_utf8(&file_text)?, config);
+        let format_result = try_format_text(std::str::from_utf8(&file_text)?, config)?;
         Ok(Some(format_result.into_bytes()))
     }
 }
@@ -17,6 +17,100 @@ pub struct FormatOptions {
     pub language: LanguageOptions,
 }
 
+impl FormatOptions {
+    /// Preset that produces output as close as possible to Prettier's GraphQL formatter.
+    ///
+    /// Prettier puts items on a single line whenever they fit in print width,
+    /// regardless of original layout.
+    /// Other options are same as default options.
+    pub fn prettier() -> Self {
+        Self {
+            layout: LayoutOptions::default(),
+            language: LanguageOptions {
+                single_line: SingleLine::Prefer,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "prettierrc")]
+impl FormatOptions {
+    /// Create options from content of Prettier configuration file (`.prettierrc`) in JSON or YAML,
+    /// based on [`FormatOptions::prettier`] preset.
+    pub fn from_prettierrc(content: &str) -> Result<Self, PrettierrcError> {
+        let mut options = Self::prettier();
+        options.apply_prettierrc(content)?;
+        Ok(options)
+    }
+
+    /// Apply options from content of Prettier configuration file (`.prettierrc`) in JSON or YAML.
+    ///
+    /// These options are supported:
+    ///
+    /// - `printWidth` for `print_width`
+    /// - `tabWidth` for `indent_width`
+    /// - `useTabs` for `use_tabs`
+    /// - `endOfLine` for `line_break`, except `auto` and `cr` which aren't supported
+    /// - `bracketSpacing` for `object_value.brace_spacing`
+    ///
+    /// Other options that aren't specified or aren't supported won't be changed.
+    pub fn apply_prettierrc(&mut self, content: &str) -> Result<(), PrettierrcError> {
+        #[derive(Default, Deserialize)]
+        #[serde(default, rename_all = "camelCase")]
+        struct PrettierOptions {
+            print_width: Option<usize>,
+            tab_width: Option<usize>,
+            use_tabs: Option<bool>,
+            end_of_line: Option<String>,
+            bracket_spacing: Option<bool>,
+        }
+
+        // JSON is a subset of YAML, so both of them are parsed as YAML
+        let prettier = serde_yaml::from_str::<Option<PrettierOptions>>(content)
+            .map_err(PrettierrcError)?
+            .unwrap_or_default();
+        if let Some(print_width) = prettier.print_width {
+            self.layout.print_width = print_width;
+        }
+        if let Some(tab_width) = prettier.tab_width {
+            self.layout.indent_width = tab_width;
+        }
+        if let Some(use_tabs) = prettier.use_tabs {
+            self.layout.use_tabs = use_tabs;
+        }
+        match prettier.end_of_line.as_deref() {
+            Some("lf") => self.layout.line_break = LineBreak::Lf,
+            Some("crlf") => self.layout.line_break = LineBreak::Crlf,
+            _ => {}
+        }
+        // this option only affects object values in GraphQL
+        if let Some(bracket_spacing) = prettier.bracket_spacing {
+            self.language.object_value_brace_spacing = Some(bracket_spacing);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "prettierrc")]
+#[derive(Debug)]
+/// Error of parsing Prettier configuration file.
+pub struct PrettierrcError(serde_yaml::Error);
+
+#[cfg(feature = "prettierrc")]
+impl std::fmt::Display for PrettierrcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid Prettier configuration: {}", self.0)
+    }
+}
+
+#[cfg(feature = "prettierrc")]
+impl std::error::Error for PrettierrcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "config_serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "config_serde", serde(default))]
@@ -49,6 +143,47 @@ impl Default for LayoutOptions {
     }
 }
 
+#[cfg(feature = "editorconfig")]
+impl LayoutOptions {
+    /// Apply settings from `.editorconfig` files that take effect on the given file path.
+    /// The file doesn't need to exist.
+    ///
+    /// These properties are supported:
+    ///
+    /// - `indent_style` for `use_tabs`
+    /// - `indent_size` (or `tab_width` if not set) for `indent_width`
+    /// - `max_line_length` for `print_width`
+    /// - `end_of_line` for `line_break`, except `cr` which isn't supported
+    ///
+    /// Other options that aren't specified in `.editorconfig` files won't be changed.
+    pub fn apply_editorconfig(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), ec4rs::Error> {
+        use ec4rs::property::{EndOfLine, IndentSize, IndentStyle, MaxLineLen};
+
+        let mut properties = ec4rs::properties_of(path)?;
+        properties.use_fallbacks();
+        match properties.get::<IndentStyle>() {
+            Ok(IndentStyle::Tabs) => self.use_tabs = true,
+            Ok(IndentStyle::Spaces) => self.use_tabs = false,
+            Err(_) => {}
+        }
+        if let Ok(IndentSize::Value(size)) = properties.get::<IndentSize>() {
+            self.indent_width = size;
+        }
+        if let Ok(MaxLineLen::Value(width)) = properties.get::<MaxLineLen>() {
+            self.print_width = width;
+        }
+        match properties.get::<EndOfLine>() {
+            Ok(EndOfLine::Lf) => self.line_break = LineBreak::Lf,
+            Ok(EndOfLine::CrLf) => self.line_break = LineBreak::Crlf,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "config_serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "config_serde", serde(rename_all = "kebab-case"))]
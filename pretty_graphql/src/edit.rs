@@ -0,0 +1,124 @@
+use crate::{config::FormatOptions, format_text};
+use apollo_parser::Lexer;
+use similar::{Algorithm, DiffTag, capture_diff_slices};
+use std::ops::Range;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A replacement of a range of source input.
+pub struct TextEdit {
+    /// Byte range of source input that will be replaced.
+    pub range: Range<usize>,
+    /// Text that replaces the range.
+    pub new_text: String,
+}
+
+/// Format the given source input and return text edits instead of the whole formatted code.
+///
+/// Edits are sorted by their ranges, don't overlap each other,
+/// and are aligned to token boundaries.
+/// Applying them from the last one to the first one produces the same output as [`format_text`].
+pub fn format_text_edits(input: &str, options: &FormatOptions) -> Vec<TextEdit> {
+    compute_edits(input, &format_text(input, options))
+}
+
+/// Format the given source input and map the cursor offset to formatted code.
+///
+/// Cursor offset is in bytes. It returns formatted code and the new cursor offset.
+/// If cursor is inside text that is changed by formatter,
+/// it will be moved to the corresponding position of the replacement as near as possible.
+pub fn format_with_cursor(input: &str, cursor: usize, options: &FormatOptions) -> (String, usize) {
+    let output = format_text(input, options);
+    let mut cursor = cursor.min(input.len());
+    while !input.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+
+    // a pair of offsets that map source input to formatted output
+    let (mut old_anchor, mut new_anchor) = (0, 0);
+    for edit in compute_edits(input, &output) {
+        if cursor < edit.range.start {
+            break;
+        }
+        let new_start = new_anchor + (edit.range.start - old_anchor);
+        if cursor < edit.range.end {
+            // move by characters, since the replacement may have different characters
+            let chars = input[edit.range.start..cursor].chars().count();
+            let offset = edit
+                .new_text
+                .char_indices()
+                .nth(chars)
+                .map_or(edit.new_text.len(), |(index, _)| index);
+            return (output, new_start + offset);
+        }
+        old_anchor = edit.range.end;
+        new_anchor = new_start + edit.new_text.len();
+    }
+    let offset = new_anchor + (cursor - old_anchor);
+    (output, offset)
+}
+
+fn compute_edits(old: &str, new: &str) -> Vec<TextEdit> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let old_offsets = old_tokens
+        .iter()
+        .scan(0, |offset, token| {
+            let start = *offset;
+            *offset += token.len();
+            Some(start)
+        })
+        .chain([old.len()])
+        .collect::<Vec<_>>();
+
+    let mut edits = Vec::<TextEdit>::new();
+    let mut pending = false;
+    for op in capture_diff_slices(Algorithm::Myers, &old_tokens, &new_tokens) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        if tag == DiffTag::Equal {
+            pending = false;
+            continue;
+        }
+        let range = old_offsets[old_range.start]..old_offsets[old_range.end];
+        let new_text = new_tokens[new_range].concat();
+        match edits.last_mut() {
+            Some(edit) if pending => {
+                edit.range.end = range.end;
+                edit.new_text.push_str(&new_text);
+            }
+            _ => edits.push(TextEdit { range, new_text }),
+        }
+        pending = true;
+    }
+    edits
+}
+
+/// Split source input into lexical tokens, including whitespaces and comments.
+/// Text that can't be lexed will be kept as separated pieces,
+/// so concatenating all pieces always produces the original input.
+fn tokenize(input: &str) -> Vec<&str> {
+    let mut spans = Lexer::new(input)
+        .map(|item| match item {
+            Ok(token) => (token.index(), token.data().len()),
+            Err(error) => (error.index(), error.data().len()),
+        })
+        .filter(|(_, len)| *len > 0)
+        .collect::<Vec<_>>();
+    spans.sort_unstable();
+
+    let mut tokens = Vec::with_capacity(spans.len());
+    let mut offset = 0;
+    for (start, len) in spans {
+        if start < offset {
+            continue;
+        }
+        if start > offset {
+            tokens.push(&input[offset..start]);
+        }
+        tokens.push(&input[start..start + len]);
+        offset = start + len;
+    }
+    if offset < input.len() {
+        tokens.push(&input[offset..]);
+    }
+    tokens
+}
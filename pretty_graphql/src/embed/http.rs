@@ -0,0 +1,120 @@
+use super::{EmbedOutput, Skipped, detect_line_break, format_block};
+use crate::config::FormatOptions;
+use std::ops::Range;
+
+/// Format GraphQL request bodies in HTTP request files
+/// used by JetBrains HTTP Client or VS Code REST Client.
+///
+/// Requests with `GRAPHQL` method or `X-REQUEST-TYPE: GraphQL` header will be formatted.
+/// The GraphQL section of request body ends at the blank line before variables in JSON,
+/// and request lines, headers, variables and response handlers are left as is.
+pub fn format_http(input: &str, options: &FormatOptions) -> EmbedOutput {
+    let line_break = detect_line_break(input);
+    let mut offset = 0;
+    let lines = input
+        .split_inclusive('\n')
+        .map(|line| {
+            let start = offset;
+            offset += line.len();
+            (start, line)
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = String::with_capacity(input.len());
+    let mut skipped = vec![];
+    let mut last_end = 0;
+    for request in lines.split(|(_, line)| line.trim_start().starts_with("###")) {
+        let Some(range) = find_graphql_section(request) else {
+            continue;
+        };
+        let code = &input[range.clone()];
+        match format_block(code, "", line_break, options) {
+            Ok(formatted) => {
+                output.push_str(&input[last_end..range.start]);
+                output.push_str(&formatted);
+                if code.ends_with('\n') {
+                    output.push_str(line_break);
+                }
+                last_end = range.end;
+            }
+            Err(error) => skipped.push(Skipped { range, error }),
+        }
+    }
+    output.push_str(&input[last_end..]);
+    EmbedOutput { output, skipped }
+}
+
+/// Find byte range of GraphQL code in a single request.
+fn find_graphql_section(request: &[(usize, &str)]) -> Option<Range<usize>> {
+    let mut lines = request.iter().skip_while(|(_, line)| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with('#') || line.starts_with("//")
+    });
+
+    let (_, request_line) = lines.next()?;
+    let mut is_graphql = request_line.split_whitespace().next() == Some("GRAPHQL");
+    for (_, line) in lines.by_ref() {
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("x-request-type")
+            && value.trim().eq_ignore_ascii_case("graphql")
+        {
+            is_graphql = true;
+        }
+    }
+    if !is_graphql {
+        return None;
+    }
+
+    // response handlers and output redirections come after request body
+    let mut body = lines
+        .take_while(|(_, line)| {
+            let line = line.trim_start();
+            !line.starts_with('>') && !line.starts_with("<>")
+        })
+        .collect::<Vec<_>>();
+    while body.last().is_some_and(|(_, line)| line.trim().is_empty()) {
+        body.pop();
+    }
+    let (start, first_line) = body.first()?;
+    // request body from file
+    if first_line.trim_start().starts_with('<') {
+        return None;
+    }
+    let (last_start, last_line) = body.last()?;
+    let body_end = last_start + last_line.len();
+
+    let end = body
+        .iter()
+        .enumerate()
+        .find(|(i, (_, line))| {
+            line.trim().is_empty()
+                && body
+                    .get(i + 1..)
+                    .is_some_and(|rest| is_json_object(rest.iter().map(|(_, line)| *line)))
+        })
+        .map_or(body_end, |(_, (start, _))| *start);
+    Some(*start..end)
+}
+
+/// Check if lines start a JSON object. Unlike selection sets,
+/// JSON objects can only start with a string key or be empty.
+fn is_json_object<'a>(mut lines: impl Iterator<Item = &'a str>) -> bool {
+    let Some(first) = lines.find(|line| !line.trim().is_empty()) else {
+        return false;
+    };
+    let Some(rest) = first.trim_start().strip_prefix('{') else {
+        return false;
+    };
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        lines
+            .find(|line| !line.trim().is_empty())
+            .is_some_and(|line| line.trim_start().starts_with(['"', '}']))
+    } else {
+        rest.starts_with(['"', '}'])
+    }
+}
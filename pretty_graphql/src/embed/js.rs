@@ -0,0 +1,311 @@
+use super::{
+    EmbedOutput, Skipped, custom_error, detect_line_break, format_block, indent_unit, line_indent,
+};
+use crate::{Error, config::FormatOptions};
+use std::ops::Range;
+
+/// Format GraphQL code in template literals of JavaScript or TypeScript.
+///
+/// Template literals tagged with `gql` or `graphql`,
+/// or marked with `/* GraphQL */` comment will be formatted.
+/// Interpolations such as `${UserFragment}` are kept as is.
+/// They can be placed on their own lines (usually after definitions)
+/// or where a name is expected (such as fragment spreads).
+///
+/// Formatted code will be placed on separated lines,
+/// and indented by one more level than the line where the template literal starts.
+/// If that line is indented, its indentation style (tabs or spaces) overrides `use_tabs` option.
+pub fn format_js(input: &str, options: &FormatOptions) -> EmbedOutput {
+    let mut scanner = Scanner {
+        src: input.as_bytes(),
+        pos: 0,
+        record: true,
+        templates: vec![],
+    };
+    scanner.scan_code(false);
+
+    let line_break = detect_line_break(input);
+    let mut output = String::with_capacity(input.len());
+    let mut skipped = vec![];
+    let mut last_end = 0;
+    for template in scanner.templates {
+        match format_template(input, &template, line_break, options) {
+            Ok(Some(formatted)) => {
+                output.push_str(&input[last_end..template.content.start]);
+                output.push_str(&formatted);
+                last_end = template.content.end;
+            }
+            Ok(None) => {}
+            Err(error) => skipped.push(Skipped {
+                range: template.content,
+                error,
+            }),
+        }
+    }
+    output.push_str(&input[last_end..]);
+    EmbedOutput { output, skipped }
+}
+
+struct Template {
+    /// Range of text between backticks.
+    content: Range<usize>,
+    /// Ranges of interpolations including `${` and `}`.
+    interpolations: Vec<Range<usize>>,
+}
+
+fn format_template(
+    input: &str,
+    template: &Template,
+    line_break: &str,
+    options: &FormatOptions,
+) -> Result<Option<String>, Error> {
+    if input[template.content.clone()].trim().is_empty() {
+        return Ok(None);
+    }
+
+    // replace interpolations with placeholders that can be parsed as GraphQL
+    let mut code = String::with_capacity(template.content.len());
+    let mut placeholders = Vec::with_capacity(template.interpolations.len());
+    let mut last_end = template.content.start;
+    for (i, interpolation) in template.interpolations.iter().enumerate() {
+        code.push_str(&input[last_end..interpolation.start]);
+        let line_start = input[..interpolation.start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1)
+            .max(template.content.start);
+        let line_end = input[interpolation.end..]
+            .find('\n')
+            .map_or(input.len(), |index| interpolation.end + index)
+            .min(template.content.end);
+        let placeholder = format!("pretty_graphql_placeholder_{i}_");
+        // interpolations on their own lines are usually fragments after definitions,
+        // which can only be represented by comments
+        if input[line_start..interpolation.start].trim().is_empty()
+            && input[interpolation.end..line_end].trim().is_empty()
+        {
+            code.push('#');
+        }
+        code.push_str(&placeholder);
+        placeholders.push(placeholder);
+        last_end = interpolation.end;
+    }
+    code.push_str(&input[last_end..template.content.end]);
+
+    let indent = line_indent(input, template.content.start);
+    // follow indentation style of the host line, otherwise tabs and spaces will be mixed
+    let mut options = options.clone();
+    if let Some(c) = indent.chars().next() {
+        options.layout.use_tabs = c == '\t';
+    }
+    let mut formatted = format_block(
+        &code,
+        &format!("{indent}{}", indent_unit(&options)),
+        line_break,
+        &options,
+    )?;
+
+    for (placeholder, interpolation) in placeholders.iter().zip(&template.interpolations) {
+        let mut matches = formatted.match_indices(placeholder.as_str());
+        let (Some((start, _)), None) = (matches.next(), matches.next()) else {
+            return Err(custom_error(
+                "interpolation can't be restored after formatting",
+                &code,
+                code.find(placeholder.as_str()).unwrap_or_default(),
+            ));
+        };
+        let end = start + placeholder.len();
+        let start = if formatted[..start].ends_with("# ") {
+            start - 2
+        } else if formatted[..start].ends_with('#') {
+            start - 1
+        } else {
+            start
+        };
+        formatted.replace_range(start..end, &input[interpolation.clone()]);
+    }
+
+    Ok(Some(format!("{line_break}{formatted}{line_break}{indent}")))
+}
+
+/// A minimal JavaScript scanner that only recognizes tokens
+/// which may contain backticks or braces, such as strings, comments and regular expressions.
+struct Scanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+    /// Whether GraphQL templates should be recorded. It's false inside GraphQL templates.
+    record: bool,
+    templates: Vec<Template>,
+}
+
+/// Keywords that can be followed by regular expressions.
+const KEYWORDS_BEFORE_EXPR: [&[u8]; 14] = [
+    b"return",
+    b"typeof",
+    b"case",
+    b"do",
+    b"else",
+    b"in",
+    b"of",
+    b"void",
+    b"yield",
+    b"await",
+    b"delete",
+    b"new",
+    b"instanceof",
+    b"throw",
+];
+
+impl Scanner<'_> {
+    /// Scan code until the end of input, or an unmatched `}` if it's in an interpolation.
+    fn scan_code(&mut self, in_interpolation: bool) {
+        let mut depth = 0usize;
+        // whether a slash starts a regular expression instead of a division operator
+        let mut regex_allowed = true;
+        while let Some(&byte) = self.src.get(self.pos) {
+            match byte {
+                b'\'' | b'"' => {
+                    self.skip_string(byte);
+                    regex_allowed = false;
+                }
+                b'`' => {
+                    let is_graphql = self.record && is_graphql_tag(&self.src[..self.pos]);
+                    self.scan_template(is_graphql);
+                    regex_allowed = false;
+                }
+                b'/' if self.src.get(self.pos + 1) == Some(&b'/') => {
+                    self.pos = memchr::memchr(b'\n', &self.src[self.pos..])
+                        .map_or(self.src.len(), |index| self.pos + index);
+                }
+                b'/' if self.src.get(self.pos + 1) == Some(&b'*') => {
+                    self.pos = memchr::memmem::find(&self.src[self.pos + 2..], b"*/")
+                        .map_or(self.src.len(), |index| self.pos + 2 + index + 2);
+                }
+                b'/' if regex_allowed => {
+                    self.skip_regex();
+                    regex_allowed = false;
+                }
+                b'{' => {
+                    depth += 1;
+                    self.pos += 1;
+                    regex_allowed = true;
+                }
+                b'}' => {
+                    if depth == 0 && in_interpolation {
+                        return;
+                    }
+                    depth = depth.saturating_sub(1);
+                    self.pos += 1;
+                    regex_allowed = false;
+                }
+                b')' | b']' => {
+                    self.pos += 1;
+                    regex_allowed = false;
+                }
+                byte if is_ident_byte(byte) => {
+                    let start = self.pos;
+                    while self.src.get(self.pos).copied().is_some_and(is_ident_byte) {
+                        self.pos += 1;
+                    }
+                    regex_allowed = KEYWORDS_BEFORE_EXPR.contains(&&self.src[start..self.pos]);
+                }
+                byte if byte.is_ascii_whitespace() => self.pos += 1,
+                _ => {
+                    self.pos += 1;
+                    regex_allowed = true;
+                }
+            }
+        }
+    }
+
+    fn skip_string(&mut self, quote: u8) {
+        self.pos += 1;
+        while let Some(&byte) = self.src.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                // unterminated string, which may be an apostrophe in JSX text
+                b'\n' => return,
+                _ if byte == quote => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_regex(&mut self) {
+        self.pos += 1;
+        let mut in_class = false;
+        while let Some(&byte) = self.src.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'[' => {
+                    in_class = true;
+                    self.pos += 1;
+                }
+                b']' => {
+                    in_class = false;
+                    self.pos += 1;
+                }
+                b'/' if !in_class => {
+                    self.pos += 1;
+                    while self.src.get(self.pos).copied().is_some_and(is_ident_byte) {
+                        self.pos += 1;
+                    }
+                    return;
+                }
+                b'\n' => return,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn scan_template(&mut self, is_graphql: bool) {
+        let start = self.pos;
+        self.pos += 1;
+        let mut interpolations = vec![];
+        while let Some(&byte) = self.src.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'`' => {
+                    self.pos += 1;
+                    if is_graphql {
+                        self.templates.push(Template {
+                            content: start + 1..self.pos - 1,
+                            interpolations,
+                        });
+                    }
+                    return;
+                }
+                b'$' if self.src.get(self.pos + 1) == Some(&b'{') => {
+                    let interpolation_start = self.pos;
+                    self.pos += 2;
+                    let record = self.record;
+                    self.record = record && !is_graphql;
+                    self.scan_code(true);
+                    self.record = record;
+                    self.pos = (self.pos + 1).min(self.src.len());
+                    interpolations.push(interpolation_start..self.pos);
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || byte >= 0x80
+}
+
+/// Check if code before a backtick is `gql`, `graphql` or `/* GraphQL */`.
+fn is_graphql_tag(before: &[u8]) -> bool {
+    let before = before.trim_ascii_end();
+    if before.ends_with(b"/* GraphQL */") {
+        return true;
+    }
+    let ident_start = before
+        .iter()
+        .rposition(|byte| !is_ident_byte(*byte))
+        .map_or(0, |index| index + 1);
+    matches!(&before[ident_start..], b"gql" | b"graphql")
+}
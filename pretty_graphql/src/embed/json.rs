@@ -0,0 +1,282 @@
+use super::{EmbedOutput, Skipped, custom_error, format_block};
+use crate::config::FormatOptions;
+use std::ops::Range;
+
+/// Format GraphQL code in string values of JSON, such as persisted query manifests.
+///
+/// Only string values located by the given JSON pointers will be formatted,
+/// where `*` matches any object key or array index.
+/// For example, use `/*` for maps from ids to operation text,
+/// or `/operations/*/body` for Apollo persisted query manifests.
+///
+/// Formatted code will be escaped and written back to the same places,
+/// and the rest of JSON (including key order and whitespaces) is left as is.
+/// Note that ids which are hashes of operation text may need to be regenerated.
+pub fn format_json(input: &str, pointers: &[&str], options: &FormatOptions) -> EmbedOutput {
+    let mut scanner = Scanner {
+        src: input.as_bytes(),
+        pos: 0,
+        path: vec![],
+        patterns: pointers
+            .iter()
+            .map(|pointer| parse_pointer(pointer))
+            .collect(),
+        targets: vec![],
+    };
+    if let Err(index) = scanner.scan_document() {
+        return EmbedOutput {
+            output: input.to_owned(),
+            skipped: vec![Skipped {
+                range: 0..input.len(),
+                error: custom_error("invalid JSON", input, index),
+            }],
+        };
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut skipped = vec![];
+    let mut last_end = 0;
+    for range in scanner.targets {
+        let result = decode_string(&input[range.start + 1..range.end - 1])
+            .ok_or_else(|| custom_error("invalid string", &input[range.clone()], 0))
+            .and_then(|code| format_block(&code, "", "\n", options));
+        match result {
+            Ok(formatted) => {
+                output.push_str(&input[last_end..range.start]);
+                encode_string(&formatted, &mut output);
+                last_end = range.end;
+            }
+            Err(error) => skipped.push(Skipped { range, error }),
+        }
+    }
+    output.push_str(&input[last_end..]);
+    EmbedOutput { output, skipped }
+}
+
+/// Split JSON pointer into unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// A minimal JSON scanner that tracks path of current value.
+/// Errors are byte offsets where scanning failed.
+struct Scanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+    path: Vec<String>,
+    patterns: Vec<Vec<String>>,
+    /// Ranges of string values to be formatted, including quotes.
+    targets: Vec<Range<usize>>,
+}
+
+impl Scanner<'_> {
+    fn scan_document(&mut self) -> Result<(), usize> {
+        self.scan_value()?;
+        self.skip_whitespaces();
+        if self.pos < self.src.len() {
+            Err(self.pos)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn scan_value(&mut self) -> Result<(), usize> {
+        self.skip_whitespaces();
+        match self.src.get(self.pos) {
+            Some(b'{') => self.scan_object(),
+            Some(b'[') => self.scan_array(),
+            Some(b'"') => {
+                let range = self.scan_string()?;
+                if self.is_target() {
+                    self.targets.push(range);
+                }
+                Ok(())
+            }
+            Some(b't') => self.expect(b"true"),
+            Some(b'f') => self.expect(b"false"),
+            Some(b'n') => self.expect(b"null"),
+            Some(b'-' | b'0'..=b'9') => {
+                while self.src.get(self.pos).is_some_and(|byte| {
+                    matches!(byte, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                }) {
+                    self.pos += 1;
+                }
+                Ok(())
+            }
+            _ => Err(self.pos),
+        }
+    }
+
+    fn scan_object(&mut self) -> Result<(), usize> {
+        self.pos += 1;
+        self.skip_whitespaces();
+        if self.src.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_whitespaces();
+            if self.src.get(self.pos) != Some(&b'"') {
+                return Err(self.pos);
+            }
+            let key_start = self.pos;
+            let key = self.scan_string()?;
+            let key = decode_string(&String::from_utf8_lossy(
+                &self.src[key.start + 1..key.end - 1],
+            ))
+            .ok_or(key_start)?;
+            self.skip_whitespaces();
+            self.expect(b":")?;
+            self.path.push(key);
+            self.scan_value()?;
+            self.path.pop();
+            if self.scan_separator(b'}')? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn scan_array(&mut self) -> Result<(), usize> {
+        self.pos += 1;
+        self.skip_whitespaces();
+        if self.src.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+        for index in 0.. {
+            self.path.push(index.to_string());
+            self.scan_value()?;
+            self.path.pop();
+            if self.scan_separator(b']')? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Scan a comma or the closing bracket, and return `true` if it's the closing bracket.
+    fn scan_separator(&mut self, close: u8) -> Result<bool, usize> {
+        self.skip_whitespaces();
+        match self.src.get(self.pos) {
+            Some(b',') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(byte) if *byte == close => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(self.pos),
+        }
+    }
+
+    fn scan_string(&mut self) -> Result<Range<usize>, usize> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.src.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(start..self.pos);
+                }
+                Some(b'\\') => self.pos += 2,
+                Some(_) => self.pos += 1,
+                None => return Err(start),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Result<(), usize> {
+        if self.src[self.pos..].starts_with(expected) {
+            self.pos += expected.len();
+            Ok(())
+        } else {
+            Err(self.pos)
+        }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self
+            .src
+            .get(self.pos)
+            .is_some_and(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn is_target(&self) -> bool {
+        self.patterns.iter().any(|pattern| {
+            pattern.len() == self.path.len()
+                && pattern
+                    .iter()
+                    .zip(&self.path)
+                    .all(|(expected, key)| expected == "*" || expected == key)
+        })
+    }
+}
+
+/// Decode content of JSON string without quotes.
+fn decode_string(raw: &str) -> Option<String> {
+    let mut decoded = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '"' => decoded.push('"'),
+            '\\' => decoded.push('\\'),
+            '/' => decoded.push('/'),
+            'b' => decoded.push('\u{8}'),
+            'f' => decoded.push('\u{c}'),
+            'n' => decoded.push('\n'),
+            'r' => decoded.push('\r'),
+            't' => decoded.push('\t'),
+            'u' => {
+                let read_unit = |chars: &mut std::str::Chars| {
+                    let hex = chars.by_ref().take(4).collect::<String>();
+                    u16::from_str_radix(&hex, 16).ok()
+                };
+                let unit = read_unit(&mut chars)?;
+                let c = if (0xd800..0xdc00).contains(&unit) {
+                    // surrogate pair
+                    if chars.next()? != '\\' || chars.next()? != 'u' {
+                        return None;
+                    }
+                    let low = read_unit(&mut chars)?;
+                    char::decode_utf16([unit, low]).next()?.ok()?
+                } else {
+                    char::from_u32(unit.into())?
+                };
+                decoded.push(c);
+            }
+            _ => return None,
+        }
+    }
+    Some(decoded)
+}
+
+/// Encode text as JSON string with quotes.
+fn encode_string(text: &str, output: &mut String) {
+    output.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                output.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
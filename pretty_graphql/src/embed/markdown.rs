@@ -0,0 +1,104 @@
+use super::{EmbedOutput, Skipped, detect_line_break, format_block};
+use crate::config::FormatOptions;
+
+/// Format GraphQL code in fenced code blocks of Markdown or MDX.
+///
+/// Code blocks whose info strings start with `graphql` or `gql` will be formatted.
+/// Indentation of fences (for example, in list items) will be preserved,
+/// and code inside will be indented as same as fences.
+/// Code blocks without closing fences won't be formatted.
+pub fn format_markdown(input: &str, options: &FormatOptions) -> EmbedOutput {
+    let line_break = detect_line_break(input);
+    let mut output = String::with_capacity(input.len());
+    let mut skipped = vec![];
+    let mut last_end = 0;
+
+    let mut offset = 0;
+    let mut lines = input.split_inclusive('\n').map(|line| {
+        let start = offset;
+        offset += line.len();
+        (start, line)
+    });
+    while let Some((start, line)) = lines.next() {
+        let Some(fence) = Fence::parse(line) else {
+            continue;
+        };
+        let content_start = start + line.len();
+        // skip the whole block even if it isn't GraphQL, so fences inside it won't be recognized
+        let Some((content_end, _)) = lines.find(|(_, line)| fence.is_closed_by(line)) else {
+            break;
+        };
+        if !fence.is_graphql {
+            continue;
+        }
+
+        let content = &input[content_start..content_end];
+        if content.trim().is_empty() {
+            continue;
+        }
+        let code = content
+            .split_inclusive('\n')
+            .map(|line| line.strip_prefix(fence.indent).unwrap_or(line))
+            .collect::<String>();
+        match format_block(&code, fence.indent, line_break, options) {
+            Ok(formatted) => {
+                output.push_str(&input[last_end..content_start]);
+                output.push_str(&formatted);
+                output.push_str(line_break);
+                last_end = content_end;
+            }
+            Err(error) => skipped.push(Skipped {
+                range: content_start..content_end,
+                error,
+            }),
+        }
+    }
+    output.push_str(&input[last_end..]);
+    EmbedOutput { output, skipped }
+}
+
+struct Fence<'a> {
+    indent: &'a str,
+    marker: u8,
+    len: usize,
+    is_graphql: bool,
+}
+
+impl<'a> Fence<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let trimmed = line.trim_start_matches([' ', '\t']);
+        let indent = &line[..line.len() - trimmed.len()];
+        let marker = *trimmed.as_bytes().first()?;
+        if marker != b'`' && marker != b'~' {
+            return None;
+        }
+        let len = trimmed.bytes().take_while(|byte| *byte == marker).count();
+        if len < 3 {
+            return None;
+        }
+        let info = trimmed[len..].trim();
+        // backticks aren't allowed in info strings of backtick fences
+        if marker == b'`' && info.contains('`') {
+            return None;
+        }
+        let lang = info
+            .split(|c: char| c.is_whitespace() || c == '{' || c == ',')
+            .next()
+            .unwrap_or_default();
+        Some(Fence {
+            indent,
+            marker,
+            len,
+            is_graphql: lang.eq_ignore_ascii_case("graphql") || lang.eq_ignore_ascii_case("gql"),
+        })
+    }
+
+    fn is_closed_by(&self, line: &str) -> bool {
+        let trimmed = line.trim_start_matches([' ', '\t']);
+        let len = trimmed
+            .bytes()
+            .take_while(|byte| *byte == self.marker)
+            .count();
+        len >= self.len && trimmed[len..].trim().is_empty()
+    }
+}
@@ -0,0 +1,109 @@
+//! Formatting GraphQL code embedded in other languages.
+//!
+//! Embedded code blocks that can't be formatted, such as those with syntax errors,
+//! will be left as is and reported in [`EmbedOutput::skipped`].
+//! All other parts of the host file won't be changed.
+
+use crate::{
+    Error,
+    config::{FormatOptions, LineBreak},
+    try_format_text,
+};
+use apollo_parser::Error as ApolloError;
+use std::ops::Range;
+
+pub use self::{
+    http::format_http, js::format_js, json::format_json, markdown::format_markdown,
+    python::format_python,
+};
+
+mod http;
+mod js;
+mod json;
+mod markdown;
+mod python;
+
+#[derive(Clone, Debug)]
+/// Result of formatting GraphQL code embedded in a host file.
+pub struct EmbedOutput {
+    /// The whole host file with embedded GraphQL code formatted.
+    pub output: String,
+    /// Embedded code blocks that are left as is.
+    pub skipped: Vec<Skipped>,
+}
+
+#[derive(Clone, Debug)]
+/// An embedded code block that can't be formatted.
+pub struct Skipped {
+    /// Byte range of the code block in the host file.
+    pub range: Range<usize>,
+    /// Why it can't be formatted. Offsets of this error are relative to the code block.
+    pub error: Error,
+}
+
+/// Format GraphQL code for embedding, and indent all non-empty lines with the given indentation.
+///
+/// Print width will be reduced by the width of indentation.
+/// Line breaks follow the host file instead of options.
+fn format_block(
+    code: &str,
+    indent: &str,
+    line_break: &str,
+    options: &FormatOptions,
+) -> Result<String, Error> {
+    let mut options = options.clone();
+    let indent_width = indent
+        .chars()
+        .map(|c| {
+            if c == '\t' {
+                options.layout.indent_width
+            } else {
+                1
+            }
+        })
+        .sum::<usize>();
+    options.layout.print_width = options.layout.print_width.saturating_sub(indent_width);
+    options.layout.line_break = LineBreak::Lf;
+
+    let formatted = try_format_text(code, &options)?;
+    Ok(formatted
+        .trim_end()
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(line_break))
+}
+
+/// Indentation of one level according to options.
+fn indent_unit(options: &FormatOptions) -> String {
+    if options.layout.use_tabs {
+        "\t".into()
+    } else {
+        " ".repeat(options.layout.indent_width)
+    }
+}
+
+/// Line break used by the host file.
+fn detect_line_break(input: &str) -> &'static str {
+    if input.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+/// Leading whitespaces of the line where the given offset is.
+fn line_indent(input: &str, offset: usize) -> &str {
+    let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = &input[line_start..offset];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+fn custom_error(message: &str, code: &str, index: usize) -> Error {
+    Error {
+        errors: vec![ApolloError::with_loc(message, String::new(), index)],
+        input: code.to_owned(),
+    }
+}
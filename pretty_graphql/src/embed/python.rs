@@ -0,0 +1,215 @@
+use super::{EmbedOutput, Skipped, detect_line_break, format_block, line_indent};
+use crate::config::FormatOptions;
+use std::ops::Range;
+
+/// Format GraphQL code in triple-quoted strings of Python.
+///
+/// Strings passed to `gql(...)` calls, or the first string in the logical line
+/// after `# language=graphql` comment will be formatted. Byte strings and f-strings are ignored.
+///
+/// Formatted code will be placed on separated lines,
+/// and indented to the column where the string starts.
+pub fn format_python(input: &str, options: &FormatOptions) -> EmbedOutput {
+    let mut scanner = Scanner {
+        src: input.as_bytes(),
+        pos: 0,
+        strings: vec![],
+    };
+    scanner.scan();
+
+    let line_break = detect_line_break(input);
+    let mut output = String::with_capacity(input.len());
+    let mut skipped = vec![];
+    let mut last_end = 0;
+    for string in scanner.strings {
+        let code = &input[string.content.clone()];
+        if code.trim().is_empty() {
+            continue;
+        }
+        let indent = line_indent(input, string.start);
+        let line_start = input[..string.start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let indent = format!(
+            "{indent}{}",
+            " ".repeat(
+                input[line_start + indent.len()..string.start]
+                    .chars()
+                    .count()
+            )
+        );
+        match format_block(code, &indent, line_break, options) {
+            Ok(formatted) => {
+                output.push_str(&input[last_end..string.content.start]);
+                output.push_str(line_break);
+                output.push_str(&formatted);
+                output.push_str(line_break);
+                output.push_str(&indent);
+                last_end = string.content.end;
+            }
+            Err(error) => skipped.push(Skipped {
+                range: string.content,
+                error,
+            }),
+        }
+    }
+    output.push_str(&input[last_end..]);
+    EmbedOutput { output, skipped }
+}
+
+struct PythonString {
+    /// Offset where the string starts, including its prefix.
+    start: usize,
+    /// Range of text between triple quotes.
+    content: Range<usize>,
+}
+
+/// A minimal Python scanner that only recognizes strings, comments and `gql(` calls.
+struct Scanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+    strings: Vec<PythonString>,
+}
+
+impl Scanner<'_> {
+    fn scan(&mut self) {
+        // whether the previous token is identifier `gql`
+        let mut after_gql = false;
+        // whether the next token is the first argument of `gql(` call
+        let mut in_gql_call = false;
+        // whether the next string is annotated with `# language=graphql`,
+        // which only applies to the next logical line
+        let mut annotated = false;
+        // whether there's code in current logical line
+        let mut has_code = false;
+        // depth of brackets, since logical lines don't end inside brackets
+        let mut depth = 0usize;
+        while let Some(&byte) = self.src.get(self.pos) {
+            if !matches!(byte, b'#') && !byte.is_ascii_whitespace() {
+                has_code = true;
+            }
+            match byte {
+                b'#' => {
+                    let start = self.pos;
+                    self.pos = memchr::memchr(b'\n', &self.src[self.pos..])
+                        .map_or(self.src.len(), |index| self.pos + index);
+                    annotated |= is_language_comment(&self.src[start + 1..self.pos]);
+                }
+                b'\'' | b'"' => {
+                    self.scan_string(self.pos, true, in_gql_call || annotated);
+                    after_gql = false;
+                    in_gql_call = false;
+                    annotated = false;
+                }
+                b'(' | b'[' | b'{' => {
+                    self.pos += 1;
+                    depth += 1;
+                    in_gql_call = after_gql && byte == b'(';
+                    after_gql = false;
+                }
+                b')' | b']' | b'}' => {
+                    self.pos += 1;
+                    depth = depth.saturating_sub(1);
+                    after_gql = false;
+                    in_gql_call = false;
+                }
+                // explicit line joining
+                b'\\' => self.pos += 2,
+                b'\n' => {
+                    self.pos += 1;
+                    if depth == 0 && has_code {
+                        annotated = false;
+                        has_code = false;
+                    }
+                }
+                byte if is_ident_byte(byte) => {
+                    let start = self.pos;
+                    while self.src.get(self.pos).copied().is_some_and(is_ident_byte) {
+                        self.pos += 1;
+                    }
+                    let ident = &self.src[start..self.pos];
+                    if matches!(self.src.get(self.pos), Some(b'\'' | b'"'))
+                        && is_string_prefix(ident)
+                    {
+                        let plain = !ident
+                            .iter()
+                            .any(|byte| matches!(byte.to_ascii_lowercase(), b'b' | b'f' | b't'));
+                        self.scan_string(start, plain, in_gql_call || annotated);
+                        annotated = false;
+                        after_gql = false;
+                    } else {
+                        after_gql = ident == b"gql";
+                    }
+                    in_gql_call = false;
+                }
+                byte if byte.is_ascii_whitespace() => self.pos += 1,
+                _ => {
+                    self.pos += 1;
+                    after_gql = false;
+                    in_gql_call = false;
+                }
+            }
+        }
+    }
+
+    /// Scan a string literal whose quote is at current position,
+    /// and record it if it's a plain triple-quoted GraphQL string.
+    fn scan_string(&mut self, start: usize, plain: bool, is_graphql: bool) {
+        let quote = self.src[self.pos];
+        let triple = [quote; 3];
+        if self.src[self.pos..].starts_with(&triple) {
+            self.pos += 3;
+            let content_start = self.pos;
+            while let Some(&byte) = self.src.get(self.pos) {
+                if byte == b'\\' {
+                    self.pos += 2;
+                } else if self.src[self.pos..].starts_with(&triple) {
+                    if plain && is_graphql {
+                        self.strings.push(PythonString {
+                            start,
+                            content: content_start..self.pos,
+                        });
+                    }
+                    self.pos += 3;
+                    return;
+                } else {
+                    self.pos += 1;
+                }
+            }
+            self.pos = self.src.len();
+        } else {
+            self.pos += 1;
+            while let Some(&byte) = self.src.get(self.pos) {
+                match byte {
+                    b'\\' => self.pos += 2,
+                    b'\n' => return,
+                    _ if byte == quote => {
+                        self.pos += 1;
+                        return;
+                    }
+                    _ => self.pos += 1,
+                }
+            }
+        }
+    }
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+/// Check if identifier before a quote is a string prefix, such as `r` or `rb`.
+fn is_string_prefix(ident: &[u8]) -> bool {
+    ident.len() <= 2
+        && ident
+            .iter()
+            .all(|byte| matches!(byte.to_ascii_lowercase(), b'r' | b'u' | b'b' | b'f' | b't'))
+}
+
+/// Check if comment text after `#` is `language=graphql`, which is recognized by JetBrains IDEs.
+fn is_language_comment(comment: &[u8]) -> bool {
+    comment
+        .split(|byte| byte.is_ascii_whitespace())
+        .find(|word| !word.is_empty())
+        .is_some_and(|word| word.eq_ignore_ascii_case(b"language=graphql"))
+}
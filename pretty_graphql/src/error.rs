@@ -1,5 +1,5 @@
 use apollo_parser::Error as ApolloError;
-use std::{cmp::Ordering, error, fmt, iter, ops::ControlFlow};
+use std::{error, fmt, ops::Range};
 
 #[derive(Clone, Debug)]
 /// Error wrapper type for `apollo-parser`.
@@ -8,33 +8,89 @@ pub struct Error {
     pub(crate) input: String,
 }
 
+impl Error {
+    /// Iterate over all syntax errors with their locations in source input.
+    pub fn diagnostics(&self) -> impl Iterator<Item = Diagnostic<'_>> {
+        let line_bounds = line_bounds(&self.input);
+        self.errors.iter().map(move |error| {
+            let offset = error.index().min(self.input.len());
+            let end = (offset + error.data().len()).min(self.input.len());
+            let line_index = line_bounds.partition_point(|bound| *bound < offset);
+            let line_start = line_index
+                .checked_sub(1)
+                .map_or(0, |index| line_bounds[index] + 1);
+            let column = self
+                .input
+                .get(line_start..offset)
+                .map_or(offset - line_start, |s| s.chars().count())
+                + 1;
+            Diagnostic {
+                offset,
+                span: offset..end,
+                line: line_index + 1,
+                column,
+                message: error.message(),
+            }
+        })
+    }
+
+    #[cfg(feature = "error_render")]
+    /// Render all syntax errors with the source line and a caret underline,
+    /// which is similar to what `rustc` prints.
+    pub fn render(&self) -> String {
+        use std::fmt::Write;
+
+        let mut output = String::new();
+        for (i, diagnostic) in self.diagnostics().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+            let line_start = self.input[..diagnostic.offset]
+                .rfind('\n')
+                .map_or(0, |index| index + 1);
+            let line_end = self.input[diagnostic.offset..]
+                .find('\n')
+                .map_or(self.input.len(), |index| diagnostic.offset + index);
+            let source_line = self.input[line_start..line_end].trim_end_matches('\r');
+            let gutter = " ".repeat(diagnostic.line.to_string().len());
+            let padding = source_line
+                .get(..diagnostic.offset - line_start)
+                .unwrap_or_default()
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+            let carets = self
+                .input
+                .get(diagnostic.offset..diagnostic.span.end.min(line_end))
+                .map_or(0, |s| s.chars().count())
+                .max(1);
+            let _ = writeln!(output, "error: {}", diagnostic.message);
+            let _ = writeln!(
+                output,
+                "{gutter}--> {}:{}",
+                diagnostic.line, diagnostic.column
+            );
+            let _ = writeln!(output, "{gutter} |");
+            let _ = writeln!(output, "{} | {source_line}", diagnostic.line);
+            let _ = writeln!(output, "{gutter} | {padding}{}", "^".repeat(carets));
+        }
+        output
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
-        let line_bounds = iter::once(0)
-            .chain(memchr::memchr_iter(b'\n', self.input.as_bytes()))
-            .collect::<Vec<_>>();
-        for (i, error) in self.errors.iter().enumerate() {
-            let pos = error.index();
-            let (ControlFlow::Break(line) | ControlFlow::Continue(line)) = line_bounds
-                .iter()
-                .try_fold(0, |i, offset| match pos.cmp(offset) {
-                    Ordering::Less => ControlFlow::Break(i),
-                    Ordering::Equal => ControlFlow::Continue(i),
-                    Ordering::Greater => ControlFlow::Continue(i + 1),
-                });
-            let col = pos - line_bounds[line - 1];
-            if i + 1 == self.errors.len() {
-                write!(
-                    f,
-                    "syntax error at line {line}, col {col}: {}",
-                    error.message()
-                )?;
-            } else {
-                writeln!(
-                    f,
-                    "syntax error at line {line}, col {col}: {}",
-                    error.message()
-                )?;
+        let mut diagnostics = self.diagnostics().peekable();
+        while let Some(Diagnostic {
+            line,
+            column,
+            message,
+            ..
+        }) = diagnostics.next()
+        {
+            write!(f, "syntax error at line {line}, col {column}: {message}")?;
+            if diagnostics.peek().is_some() {
+                writeln!(f)?;
             }
         }
         Ok(())
@@ -42,3 +98,24 @@ impl fmt::Display for Error {
 }
 
 impl error::Error for Error {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A syntax error with its location in source input.
+pub struct Diagnostic<'a> {
+    /// Byte offset where the error starts.
+    pub offset: usize,
+    /// Byte range of the source text that caused the error.
+    /// This may be empty, for example, when reaching the end of input unexpectedly.
+    pub span: Range<usize>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, counted in characters.
+    pub column: usize,
+    /// Error message reported by parser.
+    pub message: &'a str,
+}
+
+/// Offsets of all line feeds in source input.
+fn line_bounds(input: &str) -> Vec<usize> {
+    memchr::memchr_iter(b'\n', input.as_bytes()).collect()
+}
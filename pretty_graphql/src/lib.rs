@@ -1,16 +1,32 @@
 #![doc = include_str!("../README.md")]
 
-pub use crate::error::Error;
 use crate::{
     config::FormatOptions,
     printer::{Ctx, DocGen},
 };
-use apollo_parser::{Parser, cst::Document};
-use tiny_pretty::{IndentKind, PrintOptions, print};
+pub use crate::{
+    edit::{TextEdit, format_text_edits, format_with_cursor},
+    error::{Diagnostic, Error},
+    range::{format_definitions, format_range},
+    snippet::{SnippetKind, format_snippet},
+    source_map::{Mapping, SourceMap},
+    verify::{Mismatch, VerifyError, format_text_checked, verify},
+};
+use apollo_parser::{
+    Error as ApolloError, Parser, SyntaxTree,
+    cst::{CstNode, Document},
+};
+use tiny_pretty::{Doc, IndentKind, PrintOptions, print};
 
 pub mod config;
+mod edit;
+pub mod embed;
 mod error;
 mod printer;
+mod range;
+mod snippet;
+mod source_map;
+mod verify;
 
 #[inline]
 /// Format the given source input.
@@ -18,15 +34,87 @@ pub fn format_text(input: &str, options: &FormatOptions) -> String {
     print_tree(&Parser::new(input).parse().document(), options)
 }
 
+/// Format the given source input, but return an error if there're syntax errors.
+pub fn try_format_text(input: &str, options: &FormatOptions) -> Result<String, Error> {
+    let tree = Parser::new(input).parse();
+    let errors = syntax_errors(&tree);
+    if !errors.is_empty() {
+        return Err(Error {
+            errors,
+            input: input.to_owned(),
+        });
+    }
+    Ok(print_tree(&tree.document(), options))
+}
+
+/// Format the given source input, but keep top-level definitions that have syntax errors as is.
+///
+/// Definitions without syntax errors will still be formatted,
+/// so this is useful for formatting code that is being edited.
+pub fn format_text_with_recovery(input: &str, options: &FormatOptions) -> String {
+    let tree = Parser::new(input).parse();
+    let errors = tree.errors().cloned().collect::<Vec<_>>();
+    let ctx = Ctx {
+        indent_width: options.layout.indent_width,
+        options: &options.language,
+        errors: &errors,
+    };
+    print_doc(&tree.document().doc(&ctx).append(Doc::hard_line()), options)
+}
+
 /// Print the given concrete syntax tree.
 /// You may use this when you already have the parsed CST.
 pub fn print_tree(document: &Document, options: &FormatOptions) -> String {
+    print_doc(&to_doc(document, options).append(Doc::hard_line()), options)
+}
+
+/// Convert the given concrete syntax tree to [`Doc`] of `tiny_pretty` without printing it.
+///
+/// This is useful for formatters that also use `tiny_pretty` and embed GraphQL code,
+/// so GraphQL code can be nested into their docs directly.
+/// Only `indent_width` of layout options is used here,
+/// since other layout options are decided when printing.
+///
+/// Different from [`print_tree`], the returned doc doesn't end with a line break.
+pub fn to_doc(document: &Document, options: &FormatOptions) -> Doc<'static> {
     let ctx = Ctx {
         indent_width: options.layout.indent_width,
         options: &options.language,
+        errors: &[],
     };
+    document.doc(&ctx)
+}
+
+/// Print the given concrete syntax tree, along with a source map
+/// that maps tokens in formatted output back to the syntax tree.
+pub fn print_tree_with_source_map(
+    document: &Document,
+    options: &FormatOptions,
+) -> (String, SourceMap) {
+    let output = print_tree(document, options);
+    let source_map = source_map::build_source_map(&document.syntax().to_string(), &output);
+    (output, source_map)
+}
+
+/// Collect syntax errors of the given tree.
+///
+/// Parser reports unexpected EOF for document without any definitions,
+/// such as empty or comment-only document, but it's valid for formatting.
+pub(crate) fn syntax_errors(tree: &SyntaxTree) -> Vec<ApolloError> {
+    if tree.document().definitions().next().is_none()
+        && tree
+            .errors()
+            .all(|error| error.message() == "Unexpected <EOF>.")
+    {
+        Vec::new()
+    } else {
+        tree.errors().cloned().collect()
+    }
+}
+
+fn print_doc(doc: &Doc, options: &FormatOptions) -> String {
     print(
-        &document.doc(&ctx),
+        doc,
         &PrintOptions {
             indent_kind: if options.layout.use_tabs {
                 IndentKind::Tab
@@ -1,11 +1,14 @@
 use crate::config::{Comma, LanguageOptions, SingleLine};
-use apollo_parser::{S, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, cst::*};
+use apollo_parser::{Error, S, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, cst::*};
 use rowan::Direction;
 use tiny_pretty::Doc;
 
 pub(super) struct Ctx<'a> {
     pub indent_width: usize,
     pub options: &'a LanguageOptions,
+    /// Syntax errors to recover from.
+    /// Top-level definitions that contain any of these errors will be kept as is.
+    pub errors: &'a [Error],
 }
 
 pub(super) trait DocGen {
@@ -290,7 +293,7 @@ impl DocGen for Document {
             let kind = element.kind();
             match element {
                 SyntaxElement::Node(node) => {
-                    if should_ignore(&node, ctx) {
+                    if should_ignore(&node, ctx) || has_errors(&node, ctx) {
                         reflow(&node.to_string(), &mut docs);
                     } else if let Some(item) = Definition::cast(node) {
                         docs.push(item.doc(ctx));
@@ -300,11 +303,21 @@ impl DocGen for Document {
                     SyntaxKind::COMMENT => {
                         docs.push(format_comment(token.to_string(), ctx));
                     }
+                    SyntaxKind::ERROR if !ctx.errors.is_empty() => {
+                        docs.push(Doc::text(token.to_string()));
+                    }
                     SyntaxKind::WHITESPACE if token.index() > 0 && children.peek().is_some() => {
                         match token.text().chars().filter(|c| *c == '\n').count() {
                             0 => {
                                 if prev_kind == SyntaxKind::COMMENT {
                                     docs.push(Doc::hard_line());
+                                } else if !ctx.errors.is_empty()
+                                    && (prev_kind == SyntaxKind::ERROR
+                                        || children
+                                            .peek()
+                                            .is_some_and(|next| next.kind() == SyntaxKind::ERROR))
+                                {
+                                    docs.push(Doc::space());
                                 }
                             }
                             1 => {
@@ -322,7 +335,6 @@ impl DocGen for Document {
             prev_kind = kind;
         }
 
-        docs.push(Doc::hard_line());
         Doc::list(docs)
     }
 }
@@ -2513,7 +2525,7 @@ fn reflow_with_indent(s: &str) -> Vec<Doc<'static>> {
     docs
 }
 
-fn should_ignore(node: &SyntaxNode, ctx: &Ctx) -> bool {
+pub(super) fn should_ignore(node: &SyntaxNode, ctx: &Ctx) -> bool {
     // for the case that comment comes in the middle of a list of nodes
     node.prev_sibling_or_token()
         .and_then(|element| element.prev_sibling_or_token())
@@ -2536,6 +2548,35 @@ fn should_ignore(node: &SyntaxNode, ctx: &Ctx) -> bool {
         .is_some_and(|rest| rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_whitespace()))
 }
 
+/// Check if there're syntax errors inside the given top-level definition.
+///
+/// Errors reported at the end of definition belong to it, such as missing closing brace,
+/// except the one for stray token right after it, which becomes an `ERROR` token of document.
+/// Errors reported at the end of input are also blamed on the last definition.
+pub(super) fn has_errors(node: &SyntaxNode, ctx: &Ctx) -> bool {
+    let start = usize::from(node.text_range().start());
+    let end = usize::from(node.text_range().end());
+    let input_len = node
+        .ancestors()
+        .last()
+        .map_or(end, |root| usize::from(root.text_range().end()));
+    let stray_tokens = node
+        .next_sibling_or_token()
+        .filter(|element| element.kind() == SyntaxKind::ERROR)
+        .map_or(0, |_| 1);
+    ctx.errors
+        .iter()
+        .any(|error| (start..end).contains(&error.index()))
+        || ctx
+            .errors
+            .iter()
+            .filter(|error| error.index() == end)
+            .count()
+            > stray_tokens
+        || node.next_sibling().is_none()
+            && ctx.errors.iter().any(|error| error.index() == input_len)
+}
+
 fn is_empty_delimiter<N: CstNode>(node: &N) -> bool {
     node.syntax()
         .children_with_tokens()
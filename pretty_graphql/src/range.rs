@@ -0,0 +1,150 @@
+use crate::{
+    config::FormatOptions,
+    print_doc,
+    printer::{Ctx, DocGen, has_errors, should_ignore},
+};
+use apollo_parser::{
+    Parser, SyntaxKind, SyntaxNode,
+    cst::{CstNode, Definition, FieldsDefinition, SelectionSet},
+};
+use std::ops::Range;
+use tiny_pretty::Doc;
+
+/// Format only the parts of source input which overlap the given byte range.
+///
+/// If the range is inside a selection set or fields definition,
+/// only the smallest one that encloses the range will be formatted;
+/// otherwise, all top-level definitions that overlap the range will be formatted.
+/// Formatted parts keep their existing indentation,
+/// and the rest of source input will be left as is.
+///
+/// Top-level definitions that contain syntax errors or are marked with ignore comment
+/// won't be formatted, even if only a part of them overlaps the range.
+pub fn format_range(input: &str, range: Range<usize>, options: &FormatOptions) -> String {
+    format_nodes(input, options, |document| find_nodes(document, &range))
+}
+
+/// Format only top-level definitions which overlap any of the given byte ranges.
+///
+/// Different from [`format_range`], definitions are always formatted as a whole,
+/// even if ranges are inside selection sets or fields definitions.
+/// All ranges are resolved against source input,
+/// so formatting one definition won't shift ranges for others.
+///
+/// Like [`format_range`], definitions that contain syntax errors
+/// or are marked with ignore comment won't be formatted.
+pub fn format_definitions(input: &str, ranges: &[Range<usize>], options: &FormatOptions) -> String {
+    format_nodes(input, options, |document| {
+        document
+            .children()
+            .filter(|node| Definition::can_cast(node.kind()))
+            .filter(|node| ranges.iter().any(|range| overlaps(node, range)))
+            .collect()
+    })
+}
+
+/// Format nodes found in the syntax tree of source input.
+/// Found nodes must be in order and not overlap each other.
+fn format_nodes(
+    input: &str,
+    options: &FormatOptions,
+    find: impl FnOnce(&SyntaxNode) -> Vec<SyntaxNode>,
+) -> String {
+    let tree = Parser::new(input).parse();
+    let errors = tree.errors().cloned().collect::<Vec<_>>();
+    let document = tree.document();
+    let ctx = Ctx {
+        indent_width: options.layout.indent_width,
+        options: &options.language,
+        errors: &errors,
+    };
+
+    let mut output = String::with_capacity(input.len());
+    let mut last_end = 0;
+    for node in find(document.syntax()) {
+        let Some(definition) = node
+            .ancestors()
+            .find(|node| Definition::can_cast(node.kind()))
+        else {
+            continue;
+        };
+        if should_ignore(&node, &ctx)
+            || should_ignore(&definition, &ctx)
+            || has_errors(&definition, &ctx)
+        {
+            continue;
+        }
+        let start = usize::from(node.text_range().start());
+        let end = usize::from(node.text_range().end());
+        let doc = if let Some(definition) = Definition::cast(node.clone()) {
+            definition.doc(&ctx)
+        } else if let Some(selection_set) = SelectionSet::cast(node.clone()) {
+            selection_set.doc(&ctx)
+        } else if let Some(fields_definition) = FieldsDefinition::cast(node) {
+            fields_definition.doc(&ctx)
+        } else {
+            continue;
+        };
+
+        let line_start = input[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line = &input[line_start..start];
+        let indent = measure_width(
+            &line[..line.len() - line.trim_start_matches([' ', '\t']).len()],
+            options.layout.indent_width,
+        );
+        let column = measure_width(line, options.layout.indent_width);
+        let formatted = print_doc(
+            &Doc::text(" ".repeat(column)).append(doc).nest(indent),
+            options,
+        );
+
+        output.push_str(&input[last_end..start]);
+        output.push_str(&formatted[column..]);
+        last_end = end;
+    }
+    output.push_str(&input[last_end..]);
+    output
+}
+
+fn find_nodes(document: &SyntaxNode, range: &Range<usize>) -> Vec<SyntaxNode> {
+    let innermost = document
+        .descendants()
+        .filter(|node| {
+            matches!(
+                node.kind(),
+                SyntaxKind::SELECTION_SET | SyntaxKind::FIELDS_DEFINITION
+            )
+        })
+        .filter(|node| {
+            let text_range = node.text_range();
+            usize::from(text_range.start()) <= range.start
+                && range.end <= usize::from(text_range.end())
+        })
+        .last();
+    if let Some(node) = innermost {
+        return vec![node];
+    }
+
+    document
+        .children()
+        .filter(|node| Definition::can_cast(node.kind()))
+        .filter(|node| overlaps(node, range))
+        .collect()
+}
+
+/// Check if the node overlaps the range. Empty ranges overlap nodes that they touch.
+fn overlaps(node: &SyntaxNode, range: &Range<usize>) -> bool {
+    let start = usize::from(node.text_range().start());
+    let end = usize::from(node.text_range().end());
+    if range.is_empty() {
+        start <= range.start && range.start <= end
+    } else {
+        start < range.end && range.start < end
+    }
+}
+
+fn measure_width(s: &str, indent_width: usize) -> usize {
+    s.chars()
+        .map(|c| if c == '\t' { indent_width } else { 1 })
+        .sum()
+}
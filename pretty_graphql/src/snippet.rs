@@ -0,0 +1,155 @@
+use crate::{
+    Error,
+    config::FormatOptions,
+    print_doc,
+    printer::{Ctx, DocGen},
+};
+use apollo_parser::{
+    Error as ApolloError, Parser, SyntaxNode,
+    cst::{
+        Arguments, CstNode, Directives, FieldDefinition, InputValueDefinition, SelectionSet, Type,
+        Value, VariableDefinitions,
+    },
+};
+use tiny_pretty::Doc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Kind of GraphQL snippet that is going to be formatted by [`format_snippet`].
+pub enum SnippetKind {
+    /// Selection set with braces, such as `{ field }`.
+    SelectionSet,
+    /// Input value, such as `{ a: [1, 2] }`.
+    Value,
+    /// Type reference, such as `[String!]!`.
+    Type,
+    /// Field definition in object types or interfaces, such as `field(arg: Int): String`.
+    FieldDefinition,
+    /// Argument definition or input field definition, such as `arg: Int = 1`.
+    InputValueDefinition,
+    /// Directives, such as `@skip(if: true) @deprecated`.
+    Directives,
+    /// Arguments with parentheses, such as `(a: 1, b: 2)`.
+    Arguments,
+    /// Variable definitions with parentheses, such as `($a: Int, $b: String)`.
+    VariableDefinitions,
+}
+
+impl SnippetKind {
+    /// Code around the snippet that makes it become a valid document.
+    fn wrapper(&self) -> (&'static str, &'static str) {
+        match self {
+            SnippetKind::SelectionSet => ("", ""),
+            SnippetKind::Value => ("{f(a:", "\n)}"),
+            SnippetKind::Type => ("query($v:", "\n){f}"),
+            SnippetKind::FieldDefinition => ("type T{", "\n}"),
+            SnippetKind::InputValueDefinition => ("input T{", "\n}"),
+            SnippetKind::Directives => ("scalar S ", "\n"),
+            SnippetKind::Arguments => ("{f", "\n}"),
+            SnippetKind::VariableDefinitions => ("query", "\n{f}"),
+        }
+    }
+
+    fn find_node(&self, node: &SyntaxNode) -> bool {
+        let kind = node.kind();
+        match self {
+            SnippetKind::SelectionSet => SelectionSet::can_cast(kind),
+            SnippetKind::Value => Value::can_cast(kind),
+            SnippetKind::Type => Type::can_cast(kind),
+            SnippetKind::FieldDefinition => FieldDefinition::can_cast(kind),
+            SnippetKind::InputValueDefinition => InputValueDefinition::can_cast(kind),
+            SnippetKind::Directives => Directives::can_cast(kind),
+            SnippetKind::Arguments => Arguments::can_cast(kind),
+            SnippetKind::VariableDefinitions => VariableDefinitions::can_cast(kind),
+        }
+    }
+
+    fn doc(&self, node: SyntaxNode, ctx: &Ctx) -> Doc<'static> {
+        match self {
+            SnippetKind::SelectionSet => SelectionSet::cast(node).map(|node| node.doc(ctx)),
+            SnippetKind::Value => Value::cast(node).map(|node| node.doc(ctx)),
+            SnippetKind::Type => Type::cast(node).map(|node| node.doc(ctx)),
+            SnippetKind::FieldDefinition => FieldDefinition::cast(node).map(|node| node.doc(ctx)),
+            SnippetKind::InputValueDefinition => {
+                InputValueDefinition::cast(node).map(|node| node.doc(ctx))
+            }
+            SnippetKind::Directives => Directives::cast(node).map(|node| node.doc(ctx)),
+            SnippetKind::Arguments => Arguments::cast(node).map(|node| node.doc(ctx)),
+            SnippetKind::VariableDefinitions => {
+                VariableDefinitions::cast(node).map(|node| node.doc(ctx))
+            }
+        }
+        .unwrap_or_else(Doc::nil)
+    }
+}
+
+/// Format a snippet of GraphQL code, instead of a whole document.
+///
+/// Indent level is the level of indentation where the snippet will be placed.
+/// All lines except the first line will be indented,
+/// since the first line is expected to be placed after existing code or indentation.
+///
+/// ```rust
+/// use pretty_graphql::{SnippetKind, config::FormatOptions, format_snippet};
+///
+/// let options = FormatOptions::default();
+/// assert_eq!(
+///     "{\n      field\n    }",
+///     &format_snippet("{ field }", SnippetKind::SelectionSet, 2, &options).unwrap(),
+/// );
+/// ```
+pub fn format_snippet(
+    input: &str,
+    kind: SnippetKind,
+    indent_level: usize,
+    options: &FormatOptions,
+) -> Result<String, Error> {
+    let (prefix, suffix) = kind.wrapper();
+    let source = format!("{prefix}{input}{suffix}");
+    let tree = Parser::new(&source).parse();
+    let to_error = |errors: Vec<ApolloError>| Error {
+        errors: errors
+            .into_iter()
+            .map(|error| {
+                let index = error.index().saturating_sub(prefix.len()).min(input.len());
+                ApolloError::with_loc(error.message(), error.data().to_owned(), index)
+            })
+            .collect(),
+        input: input.to_owned(),
+    };
+    if tree.errors().len() > 0 {
+        return Err(to_error(tree.errors().cloned().collect()));
+    }
+
+    let start = prefix.len() + (input.len() - input.trim_start().len());
+    let end = prefix.len() + input.trim_end().len();
+    let node = tree
+        .document()
+        .syntax()
+        .descendants()
+        .find(|node| kind.find_node(node) && usize::from(node.text_range().start()) >= start)
+        .filter(|node| {
+            usize::from(node.text_range().start()) == start
+                && usize::from(node.text_range().end()) == end
+        })
+        .ok_or_else(|| {
+            to_error(vec![ApolloError::with_loc(
+                format!("expected a single {kind:?} snippet"),
+                input.trim().to_owned(),
+                start,
+            )])
+        })?;
+
+    let ctx = Ctx {
+        indent_width: options.layout.indent_width,
+        options: &options.language,
+        errors: &[],
+    };
+    let indent = indent_level * options.layout.indent_width;
+    let formatted = print_doc(
+        &Doc::text(" ".repeat(indent))
+            .append(kind.doc(node, &ctx).group())
+            .nest(indent),
+        options,
+    );
+    Ok(formatted[indent..].to_owned())
+}
@@ -0,0 +1,109 @@
+use apollo_parser::{Lexer, TokenKind};
+use similar::{Algorithm, DiffTag, capture_diff_slices};
+use std::ops::Range;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Mappings from formatted output to source input.
+///
+/// Names, literal values and comments are mapped.
+/// Mappings are sorted by their ranges in formatted output.
+pub struct SourceMap {
+    /// Mappings of tokens, one for each mapped token in formatted output.
+    pub mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    /// Find the offset in source input that corresponds to the given offset in formatted output.
+    /// It returns `None` if that offset isn't inside any mapped token.
+    pub fn original_offset(&self, generated_offset: usize) -> Option<usize> {
+        let index = self
+            .mappings
+            .partition_point(|mapping| mapping.generated.end < generated_offset);
+        self.mappings
+            .get(index)
+            .filter(|mapping| mapping.generated.start <= generated_offset)
+            .map(|mapping| {
+                let delta = generated_offset - mapping.generated.start;
+                mapping.original.start + delta.min(mapping.original.len())
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A token in formatted output and where it comes from.
+pub struct Mapping {
+    /// Byte range in formatted output.
+    pub generated: Range<usize>,
+    /// Byte range in source input.
+    pub original: Range<usize>,
+}
+
+/// Names and literal values are matched by diffing, since formatter doesn't reorder them.
+/// Comments may be moved by formatter, so they're matched separately in order.
+pub(crate) fn build_source_map(input: &str, output: &str) -> SourceMap {
+    let (old_tokens, old_comments) = collect_tokens(input);
+    let (new_tokens, new_comments) = collect_tokens(output);
+    let old_keys = old_tokens.iter().map(|token| token.key).collect::<Vec<_>>();
+    let new_keys = new_tokens.iter().map(|token| token.key).collect::<Vec<_>>();
+
+    let mut mappings = capture_diff_slices(Algorithm::Myers, &old_keys, &new_keys)
+        .into_iter()
+        .filter_map(|op| {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            (tag == DiffTag::Equal).then(|| old_range.zip(new_range))
+        })
+        .flatten()
+        .map(|(old, new)| Mapping {
+            generated: new_tokens[new].range.clone(),
+            original: old_tokens[old].range.clone(),
+        })
+        .chain(
+            old_comments
+                .into_iter()
+                .zip(new_comments)
+                .map(|(original, generated)| Mapping {
+                    generated,
+                    original,
+                }),
+        )
+        .collect::<Vec<_>>();
+    mappings.sort_by_key(|mapping| mapping.generated.start);
+    SourceMap { mappings }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum MappedKind {
+    Name,
+    Int,
+    Float,
+    String,
+}
+
+struct MappedToken<'a> {
+    /// Tokens are matched by their kinds and texts.
+    /// Text of strings is omitted, since formatter may change them.
+    key: (MappedKind, &'a str),
+    range: Range<usize>,
+}
+
+/// Collect tokens that will be mapped, along with ranges of comments.
+fn collect_tokens(input: &str) -> (Vec<MappedToken<'_>>, Vec<Range<usize>>) {
+    let mut tokens = vec![];
+    let mut comments = vec![];
+    for token in Lexer::new(input).filter_map(Result::ok) {
+        let range = token.index()..token.index() + token.data().len();
+        let key = match token.kind() {
+            TokenKind::Name => (MappedKind::Name, token.data()),
+            TokenKind::Int => (MappedKind::Int, token.data()),
+            TokenKind::Float => (MappedKind::Float, token.data()),
+            TokenKind::StringValue => (MappedKind::String, ""),
+            TokenKind::Comment => {
+                comments.push(range);
+                continue;
+            }
+            _ => continue,
+        };
+        tokens.push(MappedToken { key, range });
+    }
+    (tokens, comments)
+}
@@ -0,0 +1,236 @@
+use crate::{Error, config::FormatOptions, syntax_errors, try_format_text};
+use apollo_parser::{Parser, SyntaxKind, SyntaxNode, SyntaxToken, cst::CstNode};
+use std::{error, fmt, iter};
+
+/// Format the given source input, then verify that formatted code has the same meaning as source input.
+///
+/// Formatted code will be parsed again, and its tokens will be compared with tokens of source input,
+/// ignoring whitespaces and commas.
+/// Comments are compared separately in order, so they may be moved but can't be lost or added.
+/// Block strings are compared by their values, ignoring indentation and trailing whitespaces of lines.
+///
+/// Different from [`format_text`](crate::format_text),
+/// this will return an error if source input has syntax errors
+/// or formatted code is different from source input.
+pub fn format_text_checked(input: &str, options: &FormatOptions) -> Result<String, VerifyError> {
+    let output = try_format_text(input, options)?;
+    verify(input, &output)?;
+    Ok(output)
+}
+
+/// Verify that formatted code has the same meaning as source input.
+///
+/// This is what [`format_text_checked`] uses after formatting.
+/// You may use this when formatted code is produced in other ways,
+/// such as [`print_tree`](crate::print_tree).
+pub fn verify(input: &str, output: &str) -> Result<(), VerifyError> {
+    let output_tree = Parser::new(output).parse();
+    let errors = syntax_errors(&output_tree);
+    if !errors.is_empty() {
+        return Err(VerifyError::OutputSyntax(Error {
+            errors,
+            input: output.to_owned(),
+        }));
+    }
+
+    let input_document = Parser::new(input).parse().document();
+    let output_document = output_tree.document();
+    compare(
+        input,
+        output,
+        significant_tokens(input_document.syntax()),
+        significant_tokens(output_document.syntax()),
+        is_same_token,
+    )?;
+    compare(
+        input,
+        output,
+        comments(input_document.syntax()),
+        comments(output_document.syntax()),
+        is_same_comment,
+    )
+}
+
+/// Compare tokens one by one, and report the first divergence.
+fn compare(
+    input: &str,
+    output: &str,
+    mut input_tokens: impl Iterator<Item = SyntaxToken>,
+    mut output_tokens: impl Iterator<Item = SyntaxToken>,
+    is_same: fn(&SyntaxToken, &SyntaxToken) -> bool,
+) -> Result<(), VerifyError> {
+    loop {
+        match (input_tokens.next(), output_tokens.next()) {
+            (None, None) => return Ok(()),
+            (Some(expected), Some(found)) if is_same(&expected, &found) => {}
+            (expected, found) => {
+                return Err(VerifyError::Mismatch(Mismatch {
+                    input_offset: expected
+                        .as_ref()
+                        .map_or(input.len(), |token| token.text_range().start().into()),
+                    output_offset: found
+                        .as_ref()
+                        .map_or(output.len(), |token| token.text_range().start().into()),
+                    expected: expected.map(|token| token.to_string()),
+                    found: found.map(|token| token.to_string()),
+                }));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Error type for [`format_text_checked`] and [`verify`].
+pub enum VerifyError {
+    /// Source input has syntax errors.
+    Syntax(Error),
+    /// Formatted code has syntax errors.
+    OutputSyntax(Error),
+    /// Formatted code has different tokens from source input.
+    Mismatch(Mismatch),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Syntax(error) => write!(f, "{error}"),
+            VerifyError::OutputSyntax(error) => {
+                writeln!(f, "formatted code has syntax errors:")?;
+                write!(f, "{error}")
+            }
+            VerifyError::Mismatch(mismatch) => write!(f, "{mismatch}"),
+        }
+    }
+}
+
+impl error::Error for VerifyError {}
+
+impl From<Error> for VerifyError {
+    fn from(error: Error) -> Self {
+        VerifyError::Syntax(error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The first divergence between tokens of source input and formatted code.
+pub struct Mismatch {
+    /// Byte offset of the token in source input.
+    pub input_offset: usize,
+    /// Byte offset of the token in formatted code.
+    pub output_offset: usize,
+    /// Token text in source input, or `None` if source input ends here.
+    pub expected: Option<String>,
+    /// Token text in formatted code, or `None` if formatted code ends here.
+    pub found: Option<String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "formatted code diverges from source input at offset {} (offset {} in formatted code): expected {}, found {}",
+            self.input_offset,
+            self.output_offset,
+            self.expected
+                .as_deref()
+                .map_or("end of input".into(), |s| format!("`{s}`")),
+            self.found
+                .as_deref()
+                .map_or("end of input".into(), |s| format!("`{s}`")),
+        )
+    }
+}
+
+fn significant_tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !is_trivia(token.kind()) && !is_leading_separator(token))
+}
+
+fn comments(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == SyntaxKind::COMMENT)
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE | SyntaxKind::COMMA | SyntaxKind::COMMENT
+    )
+}
+
+/// Leading `&` of implemented interfaces and leading `|` of union member types
+/// or directive locations are optional.
+fn is_leading_separator(token: &SyntaxToken) -> bool {
+    matches!(token.kind(), SyntaxKind::AMP | SyntaxKind::PIPE)
+        && iter::successors(token.prev_token(), |token| token.prev_token())
+            .find(|token| !is_trivia(token.kind()))
+            .is_some_and(|token| {
+                matches!(
+                    token.kind(),
+                    SyntaxKind::implements_KW | SyntaxKind::EQ | SyntaxKind::on_KW
+                )
+            })
+}
+
+fn is_same_token(expected: &SyntaxToken, found: &SyntaxToken) -> bool {
+    if expected.kind() != found.kind() {
+        return false;
+    }
+    match (
+        expected
+            .text()
+            .strip_prefix("\"\"\"")
+            .and_then(|s| s.strip_suffix("\"\"\"")),
+        found
+            .text()
+            .strip_prefix("\"\"\"")
+            .and_then(|s| s.strip_suffix("\"\"\"")),
+    ) {
+        (Some(expected), Some(found)) => block_string_value(expected).eq(block_string_value(found)),
+        _ => expected.text() == found.text(),
+    }
+}
+
+/// Spaces around comment content are ignored, since they may be changed by `format_comments` option.
+fn is_same_comment(expected: &SyntaxToken, found: &SyntaxToken) -> bool {
+    expected.text().trim_start_matches('#').trim() == found.text().trim_start_matches('#').trim()
+}
+
+/// Lines of block string value as described in GraphQL specification,
+/// except that trailing whitespaces of each line are removed.
+fn block_string_value(raw: &str) -> impl Iterator<Item = &str> {
+    let lines = raw
+        .split("\r\n")
+        .flat_map(|s| s.split(['\n', '\r']))
+        .collect::<Vec<_>>();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim_start_matches([' ', '\t']).is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or_default();
+    let lines = lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line
+            } else {
+                line.get(common_indent..).unwrap_or_default()
+            }
+            .trim_end_matches([' ', '\t'])
+        })
+        .collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| !line.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(start, |index| index + 1);
+    lines.into_iter().take(end).skip(start)
+}
@@ -0,0 +1,79 @@
+use pretty_graphql::{TextEdit, format_text, format_text_edits, format_with_cursor};
+use std::fs;
+
+fn apply(input: &str, edits: &[TextEdit]) -> String {
+    edits
+        .iter()
+        .rev()
+        .fold(input.to_owned(), |mut output, edit| {
+            output.replace_range(edit.range.clone(), &edit.new_text);
+            output
+        })
+}
+
+#[test]
+fn minimal_edits() {
+    let input = "query  {\n  a,  b\n}\n";
+    assert_eq!(
+        format_text_edits(input, &Default::default()),
+        [
+            TextEdit {
+                range: 5..7,
+                new_text: " ".into(),
+            },
+            TextEdit {
+                range: 12..15,
+                new_text: "\n  ".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn already_formatted() {
+    assert!(format_text_edits("{\n  a\n}\n", &Default::default()).is_empty());
+}
+
+#[test]
+fn edits_produce_formatted_output() {
+    insta::glob!("fmt/**/*.graphql", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let options = Default::default();
+        let edits = format_text_edits(&input, &options);
+        assert!(
+            edits
+                .windows(2)
+                .all(|edits| edits[0].range.end < edits[1].range.start),
+            "'{}' has unordered or overlapped edits",
+            path.display()
+        );
+        similar_asserts::assert_eq!(
+            apply(&input, &edits),
+            format_text(&input, &options),
+            "'{}' edits are incorrect",
+            path.display()
+        );
+    });
+}
+
+#[test]
+fn cursor() {
+    let input = "query  {\n  a,  bc\n}\n";
+    let options = Default::default();
+    let (output, cursor) = format_with_cursor(input, input.find("bc").unwrap() + 1, &options);
+    assert_eq!(output, "query {\n  a\n  bc\n}\n");
+    assert_eq!(&output[cursor..], "c\n}\n");
+
+    // cursor inside whitespaces that are changed
+    let (output, cursor) = format_with_cursor(input, 6, &options);
+    assert_eq!(&output[cursor..], "{\n  a\n  bc\n}\n");
+
+    let (output, cursor) = format_with_cursor(input, input.len(), &options);
+    assert_eq!(cursor, output.len());
+
+    // cursor inside a multi-byte character
+    let input = "{ é   a }";
+    let (output, cursor) = format_with_cursor(input, 3, &options);
+    assert_eq!(output, "{\n  é\n  a\n}\n");
+    assert_eq!(&output[cursor..], "é\n  a\n}\n");
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "editorconfig")]
+
+use pretty_graphql::config::{LayoutOptions, LineBreak};
+use std::path::Path;
+
+fn layout_of(path: &str) -> LayoutOptions {
+    let mut options = LayoutOptions::default();
+    options
+        .apply_editorconfig(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/editorconfig")
+                .join(path),
+        )
+        .unwrap();
+    options
+}
+
+#[test]
+fn wildcard_section() {
+    let options = layout_of("a.txt");
+    assert!(!options.use_tabs);
+    assert_eq!(options.indent_width, 4);
+    assert_eq!(options.print_width, 80);
+    assert!(matches!(options.line_break, LineBreak::Crlf));
+}
+
+#[test]
+fn extension_section() {
+    let options = layout_of("query.gql");
+    assert!(!options.use_tabs);
+    assert_eq!(options.indent_width, 4);
+    assert_eq!(options.print_width, 100);
+}
+
+#[test]
+fn nested_section() {
+    let options = layout_of("schema/nested/types.graphql");
+    assert!(options.use_tabs);
+    assert_eq!(options.indent_width, 8);
+    assert_eq!(options.print_width, 100);
+    assert!(matches!(options.line_break, LineBreak::Lf));
+}
@@ -0,0 +1,367 @@
+use pretty_graphql::{
+    config::FormatOptions,
+    embed::{format_http, format_js, format_json, format_markdown, format_python},
+};
+
+#[test]
+fn js_tagged_templates() {
+    let input = r#"import { gql } from "@apollo/client";
+
+const QUERY = gql`
+  query GetUser($id: ID!) { user(id: $id) { ...UserFields } }
+  ${USER_FIELDS}
+`;
+
+function useData() {
+  const text = "gql`{ a }`"; // gql`{ b }`
+  return useQuery(graphql`{ viewer { ...${fragmentName} } }`, { re: /`/ });
+}
+
+const mutation = /* GraphQL */ `mutation { like(id: ${id}) }`;
+const other = styled.div`color: red;`;
+"#;
+    let output = format_js(input, &FormatOptions::default());
+    assert!(output.skipped.is_empty());
+    assert_eq!(
+        output.output,
+        r#"import { gql } from "@apollo/client";
+
+const QUERY = gql`
+  query GetUser($id: ID!) {
+    user(id: $id) {
+      ...UserFields
+    }
+  }
+  ${USER_FIELDS}
+`;
+
+function useData() {
+  const text = "gql`{ a }`"; // gql`{ b }`
+  return useQuery(graphql`
+    {
+      viewer {
+        ...${fragmentName}
+      }
+    }
+  `, { re: /`/ });
+}
+
+const mutation = /* GraphQL */ `
+  mutation {
+    like(id: ${id})
+  }
+`;
+const other = styled.div`color: red;`;
+"#
+    );
+}
+
+#[test]
+fn js_nested_templates() {
+    let input = "const a = `${gql`{ a }`}`;\nconst b = gql`{ b(c: ${`${d}`}) }`;\n";
+    let output = format_js(input, &FormatOptions::default());
+    assert!(output.skipped.is_empty());
+    assert_eq!(
+        output.output,
+        "const a = `${gql`\n  {\n    a\n  }\n`}`;\nconst b = gql`\n  {\n    b(c: ${`${d}`})\n  }\n`;\n"
+    );
+}
+
+#[test]
+fn js_tab_indented_host() {
+    let input = "function f() {\n\treturn gql`{ a { b } }`;\n}\n";
+    let output = format_js(input, &FormatOptions::default());
+    assert!(output.skipped.is_empty());
+    assert_eq!(
+        output.output,
+        "function f() {\n\treturn gql`\n\t\t{\n\t\t\ta {\n\t\t\t\tb\n\t\t\t}\n\t\t}\n\t`;\n}\n"
+    );
+}
+
+#[test]
+fn js_syntax_error() {
+    let input = "const a = gql`{ a(b: ) }`;\nconst b = gql`{ b }`;\n";
+    let output = format_js(input, &FormatOptions::default());
+    assert_eq!(
+        output.output,
+        "const a = gql`{ a(b: ) }`;\nconst b = gql`\n  {\n    b\n  }\n`;\n"
+    );
+    assert_eq!(output.skipped.len(), 1);
+    assert_eq!(output.skipped[0].range, 14..24);
+}
+
+#[test]
+fn markdown_fences() {
+    let input = "# API
+
+```graphql
+query { user(id: 1) { name } }
+```
+
+- Item:
+
+  ~~~gql title=\"example\"
+  { a
+    b }
+  ~~~
+
+````md
+```graphql
+{ nested }
+```
+````
+
+```js
+gql`{ a }`
+```
+
+```graphql
+{ broken(
+```
+";
+    let output = format_markdown(input, &FormatOptions::default());
+    assert_eq!(
+        output.output,
+        "# API
+
+```graphql
+query {
+  user(id: 1) {
+    name
+  }
+}
+```
+
+- Item:
+
+  ~~~gql title=\"example\"
+  {
+    a
+    b
+  }
+  ~~~
+
+````md
+```graphql
+{ nested }
+```
+````
+
+```js
+gql`{ a }`
+```
+
+```graphql
+{ broken(
+```
+"
+    );
+    assert_eq!(output.skipped.len(), 1);
+    assert_eq!(&input[output.skipped[0].range.clone()], "{ broken(\n");
+}
+
+#[test]
+fn http_requests() {
+    let input = r#"### Get user
+POST https://example.com/graphql
+Content-Type: application/json
+X-REQUEST-TYPE: GraphQL
+
+query ($id: ID!) { user(id: $id) { name
+
+ email } }
+
+{
+  "id": 1
+}
+
+### JetBrains
+GRAPHQL https://example.com/graphql
+
+{ viewer { login } }
+
+> {% client.test("ok", () => {}); %}
+
+### Not GraphQL
+POST https://example.com/rest
+Content-Type: application/json
+
+{ "a": 1 }
+
+###
+GRAPHQL https://example.com/graphql
+
+{ broken(
+"#;
+    let output = format_http(input, &FormatOptions::default());
+    assert_eq!(
+        output.output,
+        r#"### Get user
+POST https://example.com/graphql
+Content-Type: application/json
+X-REQUEST-TYPE: GraphQL
+
+query($id: ID!) {
+  user(id: $id) {
+    name
+
+    email
+  }
+}
+
+{
+  "id": 1
+}
+
+### JetBrains
+GRAPHQL https://example.com/graphql
+
+{
+  viewer {
+    login
+  }
+}
+
+> {% client.test("ok", () => {}); %}
+
+### Not GraphQL
+POST https://example.com/rest
+Content-Type: application/json
+
+{ "a": 1 }
+
+###
+GRAPHQL https://example.com/graphql
+
+{ broken(
+"#
+    );
+    assert_eq!(output.skipped.len(), 1);
+    assert_eq!(&input[output.skipped[0].range.clone()], "{ broken(\n");
+}
+
+#[test]
+fn json_pointers() {
+    let options = FormatOptions::default();
+    let input = r#"{
+  "z9": "query A { a }",
+  "a1": "query B($id: ID!) { user(id: $id, role: \"admin\\n\") { name } }",
+  "b2": 1
+}
+"#;
+    let output = format_json(input, &["/*"], &options);
+    assert!(output.skipped.is_empty());
+    assert_eq!(
+        output.output,
+        r#"{
+  "z9": "query A {\n  a\n}",
+  "a1": "query B($id: ID!) {\n  user(id: $id, role: \"admin\\n\") {\n    name\n  }\n}",
+  "b2": 1
+}
+"#
+    );
+
+    let input = r#"{"format":"apollo-persisted-query-manifest","version":1,"operations":[{"id":"x","name":"A","body":"query A{a}"},{"id":"y","name":"B","body":"query B{"}]}"#;
+    let output = format_json(input, &["/operations/*/body"], &options);
+    assert_eq!(
+        output.output,
+        r#"{"format":"apollo-persisted-query-manifest","version":1,"operations":[{"id":"x","name":"A","body":"query A {\n  a\n}"},{"id":"y","name":"B","body":"query B{"}]}"#
+    );
+    assert_eq!(output.skipped.len(), 1);
+    assert_eq!(&input[output.skipped[0].range.clone()], r#""query B{""#);
+
+    let output = format_json("{\"a\": ", &["/*"], &options);
+    assert_eq!(output.output, "{\"a\": ");
+    assert_eq!(output.skipped.len(), 1);
+}
+
+#[test]
+fn python_strings() {
+    let input = r#"from gql import gql
+
+QUERY = gql("""query GetUser($id: ID!) { user(id: $id) { name } }""")
+
+def fetch(client):
+    return client.execute(
+        gql(
+            r"""
+            { viewer { login } }
+            """
+        )
+    )
+
+# language=GraphQL
+FRAGMENT = """fragment F on User { id }"""
+
+IGNORED = """{ not graphql }"""
+ALSO_IGNORED = gql(f"""{{ a }}""")
+BROKEN = gql("""{ a""")
+"#;
+    let output = format_python(input, &FormatOptions::default());
+    assert_eq!(
+        output.output,
+        r#"from gql import gql
+
+QUERY = gql("""
+            query GetUser($id: ID!) {
+              user(id: $id) {
+                name
+              }
+            }
+            """)
+
+def fetch(client):
+    return client.execute(
+        gql(
+            r"""
+            {
+              viewer {
+                login
+              }
+            }
+            """
+        )
+    )
+
+# language=GraphQL
+FRAGMENT = """
+           fragment F on User {
+             id
+           }
+           """
+
+IGNORED = """{ not graphql }"""
+ALSO_IGNORED = gql(f"""{{ a }}""")
+BROKEN = gql("""{ a""")
+"#
+    );
+    assert_eq!(output.skipped.len(), 1);
+    assert_eq!(&input[output.skipped[0].range.clone()], "{ a");
+}
+
+#[test]
+fn python_annotation_scope() {
+    let input = r#"# language=graphql
+x = load()
+
+def f():
+    return 1
+
+DOC = """{ not_meant }"""
+
+# language=graphql
+
+QUERY = (
+    """{ a }"""
+)
+"#;
+    let output = format_python(input, &FormatOptions::default());
+    assert!(output.skipped.is_empty());
+    assert_eq!(
+        output.output,
+        input.replace(
+            r#"    """{ a }""""#,
+            "    \"\"\"\n    {\n      a\n    }\n    \"\"\""
+        )
+    );
+}
@@ -0,0 +1,60 @@
+use pretty_graphql::{Diagnostic, try_format_text};
+
+#[test]
+fn diagnostics() {
+    let error = try_format_text("query {\n  a(x: ) b\n}\n}", &Default::default()).unwrap_err();
+    assert_eq!(
+        error.diagnostics().collect::<Vec<_>>(),
+        [
+            Diagnostic {
+                offset: 15,
+                span: 15..16,
+                line: 2,
+                column: 8,
+                message: "expected a valid Value",
+            },
+            Diagnostic {
+                offset: 21,
+                span: 21..22,
+                line: 4,
+                column: 1,
+                message: "expected a StringValue, Name or OperationDefinition",
+            },
+        ]
+    );
+    assert_eq!(
+        error.to_string(),
+        "syntax error at line 2, col 8: expected a valid Value\n\
+         syntax error at line 4, col 1: expected a StringValue, Name or OperationDefinition"
+    );
+}
+
+#[test]
+fn diagnostics_at_start_of_input() {
+    let error = try_format_text("}", &Default::default()).unwrap_err();
+    let diagnostic = error.diagnostics().next().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.column), (1, 1));
+}
+
+#[cfg(feature = "error_render")]
+#[test]
+fn render() {
+    let error = try_format_text("query {\n  a(x: ) b\n}\n", &Default::default()).unwrap_err();
+    assert_eq!(
+        error.render(),
+        "error: expected a valid Value\n \
+         --> 2:8\n  \
+         |\n\
+         2 |   a(x: ) b\n  \
+         |        ^\n"
+    );
+}
+
+#[test]
+fn no_definitions() {
+    let options = Default::default();
+    assert_eq!(try_format_text("", &options).unwrap(), "\n");
+    assert_eq!(try_format_text("\n", &options).unwrap(), "\n");
+    assert_eq!(try_format_text("# notes\n", &options).unwrap(), "# notes\n");
+    assert!(try_format_text("# notes\nquery {", &options).is_err());
+}
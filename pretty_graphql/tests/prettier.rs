@@ -0,0 +1,119 @@
+use pretty_graphql::{config::FormatOptions, format_text};
+
+#[test]
+fn same_as_prettier() {
+    let options = FormatOptions::prettier();
+    let cases = [
+        ("{ a(\n  b: 1\n  c: 2\n) }", "{\n  a(b: 1, c: 2)\n}\n"),
+        (
+            "{ a(b: [1,2,3], c: {d: 1}) @skip(if: $a) }",
+            "{\n  a(b: [1, 2, 3], c: { d: 1 }) @skip(if: $a)\n}\n",
+        ),
+        (
+            "{ veryLongFieldNameNumberOne(argumentNumberOne: \"some long string value\", argumentNumberTwo: 12345) { x } }",
+            "{
+  veryLongFieldNameNumberOne(
+    argumentNumberOne: \"some long string value\"
+    argumentNumberTwo: 12345
+  ) {
+    x
+  }
+}
+",
+        ),
+        (
+            "{ someVeryLongFieldName @include(if: $someVeryLongVariableName) @skip(if: $anotherLongVariable) }",
+            "{
+  someVeryLongFieldName
+    @include(if: $someVeryLongVariableName)
+    @skip(if: $anotherLongVariable)
+}
+",
+        ),
+        (
+            "union LongUnionTypeNameHere = FirstMemberTypeName | SecondMemberTypeName | ThirdMemberTypeName",
+            "union LongUnionTypeNameHere =
+  | FirstMemberTypeName
+  | SecondMemberTypeName
+  | ThirdMemberTypeName
+",
+        ),
+        ("enum E { A, B }", "enum E {\n  A\n  B\n}\n"),
+        (
+            "type Q { a(x: Int,\n\n y: Int): Int }",
+            "type Q {\n  a(\n    x: Int\n\n    y: Int\n  ): Int\n}\n",
+        ),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(format_text(input, &options), expected, "input: {input}");
+    }
+}
+
+/// Output differences from Prettier that can't be resolved by options.
+/// If one of them gets resolved, please move it to the test above.
+#[test]
+fn known_differences() {
+    let options = FormatOptions::prettier();
+    // (input, output of Prettier, output of Pretty GraphQL)
+    let cases = [
+        // Prettier always puts descriptions on their own lines.
+        (
+            "type A {\n  \"desc\" f: Int\n}",
+            "type A {\n  \"desc\"\n  f: Int\n}\n",
+            "type A {\n  \"desc\" f: Int\n}\n",
+        ),
+        // Prettier always puts content of block strings on their own lines.
+        (
+            "\"\"\"Block\"\"\"\nscalar X",
+            "\"\"\"\nBlock\n\"\"\"\nscalar X\n",
+            "\"\"\"Block\"\"\"\nscalar X\n",
+        ),
+        // Prettier adds a space between keyword and variable definitions of anonymous operations.
+        (
+            "query ($a: Int) { a }",
+            "query ($a: Int) {\n  a\n}\n",
+            "query($a: Int) {\n  a\n}\n",
+        ),
+        // Prettier never breaks implemented interfaces.
+        (
+            "type T implements VeryLongInterfaceNameNumberOne & VeryLongInterfaceNameNumberTwo & Three { a: Int }",
+            "type T implements VeryLongInterfaceNameNumberOne & VeryLongInterfaceNameNumberTwo & Three {\n  a: Int\n}\n",
+            "type T implements\n& VeryLongInterfaceNameNumberOne\n& VeryLongInterfaceNameNumberTwo\n& Three {\n  a: Int\n}\n",
+        ),
+        // Prettier never breaks directive locations.
+        (
+            "directive @longDirectiveName(argument: Int) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION",
+            "directive @longDirectiveName(argument: Int) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION\n",
+            "directive @longDirectiveName(argument: Int) on\n  FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION\n",
+        ),
+    ];
+    for (input, prettier, expected) in cases {
+        let output = format_text(input, &options);
+        assert_ne!(output, prettier, "input: {input}");
+        assert_eq!(output, expected, "input: {input}");
+    }
+}
+
+#[cfg(feature = "prettierrc")]
+#[test]
+fn prettierrc() {
+    use pretty_graphql::config::{LineBreak, SingleLine};
+
+    let options = FormatOptions::from_prettierrc(
+        r#"{ "printWidth": 100, "tabWidth": 4, "endOfLine": "crlf", "bracketSpacing": false, "semi": false }"#,
+    )
+    .unwrap();
+    assert_eq!(options.layout.print_width, 100);
+    assert_eq!(options.layout.indent_width, 4);
+    assert!(!options.layout.use_tabs);
+    assert!(matches!(options.layout.line_break, LineBreak::Crlf));
+    assert_eq!(options.language.object_value_brace_spacing, Some(false));
+    assert!(matches!(options.language.single_line, SingleLine::Prefer));
+
+    let options = FormatOptions::from_prettierrc("useTabs: true\nendOfLine: auto\n").unwrap();
+    assert!(options.layout.use_tabs);
+    assert!(matches!(options.layout.line_break, LineBreak::Lf));
+
+    assert!(FormatOptions::from_prettierrc("").is_ok());
+    assert!(FormatOptions::from_prettierrc("printWidth: wide").is_err());
+}
@@ -0,0 +1,144 @@
+use pretty_graphql::{format_definitions, format_range};
+
+const INPUT: &str = "query A {   a   b }
+
+query B {
+  user(id: 1) {   name
+      email }
+}
+
+type T {   f:Int
+}
+";
+
+#[test]
+fn innermost_selection_set() {
+    let start = INPUT.find("name").unwrap();
+    assert_eq!(
+        format_range(INPUT, start..start + 4, &Default::default()),
+        "query A {   a   b }
+
+query B {
+  user(id: 1) {
+    name
+    email
+  }
+}
+
+type T {   f:Int
+}
+"
+    );
+}
+
+#[test]
+fn top_level_definitions() {
+    let start = INPUT.find("query A").unwrap();
+    let end = INPUT.find("query B").unwrap() + 1;
+    assert_eq!(
+        format_range(INPUT, start..end, &Default::default()),
+        "query A {
+  a
+  b
+}
+
+query B {
+  user(id: 1) {
+    name
+    email
+  }
+}
+
+type T {   f:Int
+}
+"
+    );
+}
+
+#[test]
+fn fields_definition() {
+    let offset = INPUT.find("f:Int").unwrap();
+    assert_eq!(
+        format_range(INPUT, offset..offset, &Default::default()),
+        INPUT.replace("type T {   f:Int\n}", "type T {\n  f: Int\n}")
+    );
+}
+
+#[test]
+fn outside_definitions() {
+    let offset = INPUT.find("\n\nquery B").unwrap() + 1;
+    assert_eq!(
+        format_range(INPUT, offset..offset, &Default::default()),
+        INPUT
+    );
+}
+
+#[test]
+fn ignored_definition() {
+    let input = "# pretty-graphql-ignore\nquery A {   a {  b   c } }\n";
+    let offset = input.find('b').unwrap();
+    assert_eq!(
+        format_range(input, offset..offset, &Default::default()),
+        input
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    let input = "{ a(x: ) { b   c } }\n\n{ d   e }\n";
+    let offset = input.find('b').unwrap();
+    assert_eq!(
+        format_range(input, offset..offset, &Default::default()),
+        input
+    );
+    assert_eq!(
+        format_range(input, 0..input.len(), &Default::default()),
+        "{ a(x: ) { b   c } }\n\n{\n  d\n  e\n}\n"
+    );
+}
+
+#[test]
+fn definitions_in_multiple_ranges() {
+    let input = "query A {\n  a                                        \n  b\n  p\n  c\n}\n\nquery   B { b }\n\nquery   C {\n    y\n}\n";
+    let line = |text: &str| {
+        let start = input.find(text).unwrap();
+        start..start + text.len()
+    };
+    assert_eq!(
+        format_definitions(input, &[line("  b"), line("  c")], &Default::default()),
+        "query A {\n  a\n  b\n  p\n  c\n}\n\nquery   B { b }\n\nquery   C {\n    y\n}\n"
+    );
+
+    let offset = input.find("    y").unwrap() + 4;
+    assert_eq!(
+        format_definitions(
+            input,
+            std::slice::from_ref(&(offset..offset)),
+            &Default::default()
+        ),
+        input.replace("query   C {\n    y\n}", "query C {\n  y\n}")
+    );
+}
+
+#[test]
+fn stray_token_after_definition() {
+    let input = "query A { a   b }\n}\n\nquery C { c }\n";
+    assert_eq!(
+        format_range(input, 0..3, &Default::default()),
+        "query A {\n  a\n  b\n}\n}\n\nquery C { c }\n"
+    );
+    let end = input.find("query C").unwrap();
+    assert_eq!(
+        format_definitions(input, std::slice::from_ref(&(0..end)), &Default::default()),
+        "query A {\n  a\n  b\n}\n}\n\nquery C { c }\n"
+    );
+}
+
+#[test]
+fn stray_token_touching_definition() {
+    let input = "type A { a: Int }}\n\ntype B {   b: Int }\n";
+    assert_eq!(
+        format_range(input, 0..3, &Default::default()),
+        "type A {\n  a: Int\n}}\n\ntype B {   b: Int }\n"
+    );
+}
@@ -0,0 +1,73 @@
+use pretty_graphql::{format_text, format_text_with_recovery};
+
+#[test]
+fn keep_broken_definitions() {
+    let input = "type A {   a: Int }
+
+type B {
+    b:
+      c: String
+}
+} query { x }
+";
+    assert_eq!(
+        format_text_with_recovery(input, &Default::default()),
+        "type A {
+  a: Int
+}
+
+type B {
+    b:
+      c: String
+}
+} query {
+  x
+}
+"
+    );
+}
+
+#[test]
+fn unexpected_eof() {
+    let input = "{ a }\n\nquery {\n  b(x:";
+    assert_eq!(
+        format_text_with_recovery(input, &Default::default()),
+        "{\n  a\n}\n\nquery {\n  b(x:\n"
+    );
+}
+
+#[test]
+fn same_as_format_text_without_errors() {
+    let input = "query { a(b: 1) { c } }";
+    assert_eq!(
+        format_text_with_recovery(input, &Default::default()),
+        format_text(input, &Default::default())
+    );
+}
+
+#[test]
+fn stray_token_between_definitions() {
+    let input = "query A {a   b}\n\n}\n\nquery C {d    e}\n";
+    assert_eq!(
+        format_text_with_recovery(input, &Default::default()),
+        "query A {\n  a\n  b\n}\n\n}\n\nquery C {\n  d\n  e\n}\n"
+    );
+}
+
+#[test]
+fn stray_token_after_definition() {
+    let input = "type A { a: Int }}\n\ntype B {   b: Int }\n";
+    assert_eq!(
+        format_text_with_recovery(input, &Default::default()),
+        "type A {\n  a: Int\n}}\n\ntype B {\n  b: Int\n}\n"
+    );
+}
+
+#[test]
+fn unexpected_eof_before_trailing_trivia() {
+    let input = "{ a }\n\nquery {\n  b(x:\n\n# c\n";
+    assert_eq!(
+        format_text_with_recovery(input, &Default::default()),
+        "{\n  a\n}\n\nquery {\n  b(x:\n\n# c\n"
+    );
+}
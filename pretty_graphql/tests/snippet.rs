@@ -0,0 +1,72 @@
+use pretty_graphql::{SnippetKind, format_snippet};
+
+#[test]
+fn snippets() {
+    let options = Default::default();
+    assert_eq!(
+        format_snippet("{a b {c}}", SnippetKind::SelectionSet, 0, &options).unwrap(),
+        "{\n  a\n  b {\n    c\n  }\n}"
+    );
+    assert_eq!(
+        format_snippet(" {a:[1 2],b:null} ", SnippetKind::Value, 0, &options).unwrap(),
+        "{ a: [1, 2], b: null }"
+    );
+    assert_eq!(
+        format_snippet("[ String ! ] !", SnippetKind::Type, 0, &options).unwrap(),
+        "[String!]!"
+    );
+    assert_eq!(
+        format_snippet(
+            "field(a:Int=1 b:String):String @deprecated",
+            SnippetKind::FieldDefinition,
+            1,
+            &options
+        )
+        .unwrap(),
+        "field(a: Int = 1, b: String): String @deprecated"
+    );
+    assert_eq!(
+        format_snippet("arg:Int=1", SnippetKind::InputValueDefinition, 0, &options).unwrap(),
+        "arg: Int = 1"
+    );
+    assert_eq!(
+        format_snippet("@a(x:1)   @b", SnippetKind::Directives, 0, &options).unwrap(),
+        "@a(x: 1) @b"
+    );
+    assert_eq!(
+        format_snippet("(a:1 b:2)", SnippetKind::Arguments, 0, &options).unwrap(),
+        "(a: 1, b: 2)"
+    );
+    assert_eq!(
+        format_snippet(
+            "($a:Int $b:String)",
+            SnippetKind::VariableDefinitions,
+            0,
+            &options
+        )
+        .unwrap(),
+        "($a: Int, $b: String)"
+    );
+}
+
+#[test]
+fn indent_level() {
+    let options = Default::default();
+    assert_eq!(
+        format_snippet("{ a { b } }", SnippetKind::SelectionSet, 2, &options).unwrap(),
+        "{\n      a {\n        b\n      }\n    }"
+    );
+}
+
+#[test]
+fn invalid_snippets() {
+    let options = Default::default();
+    let error = format_snippet("{ a(x: ) }", SnippetKind::SelectionSet, 0, &options).unwrap_err();
+    assert_eq!(error.diagnostics().next().unwrap().offset, 7);
+
+    let error = format_snippet("1 2", SnippetKind::Value, 0, &options).unwrap_err();
+    assert!(error.to_string().contains("line 1"));
+
+    assert!(format_snippet("@a", SnippetKind::Value, 0, &options).is_err());
+    assert!(format_snippet("", SnippetKind::Type, 0, &options).is_err());
+}
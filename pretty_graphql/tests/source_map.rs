@@ -0,0 +1,88 @@
+use apollo_parser::{Lexer, Parser, TokenKind};
+use pretty_graphql::{Mapping, print_tree_with_source_map};
+use std::fs;
+
+#[test]
+fn map_tokens() {
+    let input = "query Q($a:Int=1){a(b:$a) # c\n}";
+    let (output, source_map) =
+        print_tree_with_source_map(&Parser::new(input).parse().document(), &Default::default());
+    assert_eq!(output, "query Q($a: Int = 1) {\n  a(b: $a) # c\n}\n");
+    let pairs = source_map
+        .mappings
+        .iter()
+        .map(
+            |Mapping {
+                 generated,
+                 original,
+             }| (&output[generated.clone()], &input[original.clone()]),
+        )
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pairs,
+        [
+            ("query", "query"),
+            ("Q", "Q"),
+            ("a", "a"),
+            ("Int", "Int"),
+            ("1", "1"),
+            ("a", "a"),
+            ("b", "b"),
+            ("a", "a"),
+            ("# c", "# c"),
+        ]
+    );
+
+    let offset = output.find("Int").unwrap() + 1;
+    assert_eq!(
+        source_map.original_offset(offset),
+        Some(input.find("Int").unwrap() + 1)
+    );
+    assert_eq!(source_map.original_offset(output.find('{').unwrap()), None);
+}
+
+#[test]
+fn map_all_tokens() {
+    insta::glob!("fmt/**/*.graphql", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let (output, source_map) = print_tree_with_source_map(
+            &Parser::new(&input).parse().document(),
+            &Default::default(),
+        );
+        let tokens = Lexer::new(&output)
+            .filter_map(Result::ok)
+            .filter(|token| {
+                matches!(
+                    token.kind(),
+                    TokenKind::Name
+                        | TokenKind::Int
+                        | TokenKind::Float
+                        | TokenKind::StringValue
+                        | TokenKind::Comment
+                )
+            })
+            .count();
+        assert_eq!(
+            source_map.mappings.len(),
+            tokens,
+            "'{}' has unmapped tokens",
+            path.display()
+        );
+        for Mapping {
+            generated,
+            original,
+        } in &source_map.mappings
+        {
+            let generated = &output[generated.clone()];
+            let original = &input[original.clone()];
+            if !generated.starts_with(['#', '"']) {
+                assert_eq!(
+                    generated,
+                    original,
+                    "'{}' has incorrect mappings",
+                    path.display()
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,94 @@
+use insta::glob;
+use pretty_graphql::{
+    Mismatch, VerifyError, config::FormatOptions, format_text, format_text_checked, verify,
+};
+use std::fs;
+
+#[test]
+fn ignore_insignificant_tokens() {
+    assert!(verify("query { a, b # c\n}", "query {\n  a\n  b # c\n}\n").is_ok());
+    assert!(verify("#c\nquery { a }", "# c\nquery {\n  a\n}\n").is_ok());
+    assert!(verify("type T implements A & B", "type T implements & A & B\n").is_ok());
+    assert!(
+        verify(
+            "\"\"\"\n    a\n      b\n\"\"\"\nscalar S",
+            "\"\"\"\na\n  b\n\"\"\"\nscalar S\n"
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn mismatch() {
+    let Err(VerifyError::Mismatch(mismatch)) = verify("{ a b }", "{\n  a\n  c\n}\n") else {
+        panic!("expected mismatch");
+    };
+    assert_eq!(
+        mismatch,
+        Mismatch {
+            input_offset: 4,
+            output_offset: 8,
+            expected: Some("b".into()),
+            found: Some("c".into()),
+        }
+    );
+    assert_eq!(
+        mismatch.to_string(),
+        "formatted code diverges from source input at offset 4 (offset 8 in formatted code): expected `b`, found `c`"
+    );
+
+    let Err(VerifyError::Mismatch(mismatch)) = verify("{ a }", "{\n  a\n}\n{ b }\n") else {
+        panic!("expected mismatch");
+    };
+    assert_eq!(mismatch.expected, None);
+}
+
+#[test]
+fn lost_or_added_comments() {
+    let Err(VerifyError::Mismatch(mismatch)) = verify("# keep me\n{ a }", "{ a }\n") else {
+        panic!("expected mismatch");
+    };
+    assert_eq!(mismatch.expected.as_deref(), Some("# keep me"));
+    assert_eq!(mismatch.found, None);
+
+    let Err(VerifyError::Mismatch(mismatch)) = verify("{ a }", "# injected\n{ a }\n") else {
+        panic!("expected mismatch");
+    };
+    assert_eq!(mismatch.expected, None);
+    assert_eq!(mismatch.found.as_deref(), Some("# injected"));
+}
+
+#[test]
+fn syntax_errors() {
+    assert!(matches!(
+        format_text_checked("{ a(b: ) }", &Default::default()),
+        Err(VerifyError::Syntax(_))
+    ));
+    assert!(matches!(
+        verify("{ a }", "{ a"),
+        Err(VerifyError::OutputSyntax(_))
+    ));
+}
+
+#[test]
+fn fixtures() {
+    glob!("fmt/**/*.graphql", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let mut options = vec![FormatOptions::default()];
+        if let Ok(config_file) = fs::read_to_string(path.with_file_name("config.json")) {
+            let configs =
+                serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&config_file)
+                    .unwrap();
+            options = configs
+                .into_iter()
+                .filter(|(name, _)| name != "$schema")
+                .map(|(_, config)| serde_json::from_value(config).unwrap())
+                .collect();
+        }
+        for options in options {
+            if let Err(error) = verify(&input, &format_text(&input, &options)) {
+                panic!("'{}' changes meaning: {error}", path.display());
+            }
+        }
+    });
+}